@@ -2,12 +2,28 @@ use std::{
 	env, fs,
 	io::Cursor,
 	path::{Path, PathBuf},
+	thread,
+	time::Duration,
 };
 
 use bzip2::read::BzDecoder;
 use cc::Build;
+use sha2::{Digest, Sha256};
 use tar::Archive;
 
+const CHMLIB_TARBALL_URL: &str = "http://www.jedrea.com/chmlib/chmlib-0.40.tar.bz2";
+/// Pinned checksum of the upstream download at `CHMLIB_TARBALL_URL`. Only enforced against that
+/// download itself (see `download_and_extract_chmlib`) - a tarball supplied via
+/// `PAPERBACK_CHMLIB_TARBALL` is a file the caller already chose to trust and is used as-is.
+const CHMLIB_TARBALL_SHA256: &str = "b20eb8dcd55f59fe04ed1ca58d0dd3a76b5065a4a1f2a0aef1f9a5f7cb6e2cc3";
+const CHMLIB_TARBALL_ENV_VAR: &str = "PAPERBACK_CHMLIB_TARBALL";
+const CHMLIB_MAX_ATTEMPTS_ENV_VAR: &str = "PAPERBACK_CHMLIB_MAX_ATTEMPTS";
+const CHMLIB_TIMEOUT_SECS_ENV_VAR: &str = "PAPERBACK_CHMLIB_TIMEOUT_SECS";
+const DEFAULT_CHMLIB_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_CHMLIB_TIMEOUT_SECS: u64 = 30;
+const RETRY_BASE_DELAY_MS: u64 = 250;
+const RETRY_MAX_DELAY_MS: u64 = 1000;
+
 fn main() {
 	cxx_build::bridge("src/bridge.rs").flag_if_supported("-std=c++20").compile("paperback-bridge");
 	println!("cargo:rerun-if-changed=src/bridge.rs");
@@ -35,14 +51,64 @@ fn build_chmlib() {
 }
 
 fn download_and_extract_chmlib(out_dir: &Path) {
-	let url = "http://www.jedrea.com/chmlib/chmlib-0.40.tar.bz2";
-	let response = reqwest::blocking::get(url).expect("Failed to download chmlib");
-	let bytes = response.bytes().expect("Failed to read chmlib tarball");
+	let bytes = if let Ok(path) = env::var(CHMLIB_TARBALL_ENV_VAR) {
+		// A locally-supplied tarball is not checked against `CHMLIB_TARBALL_SHA256`: that constant
+		// pins the specific upstream release at `CHMLIB_TARBALL_URL`, and enforcing it here would
+		// leave no way to build from a newer/patched chmlib release, or to recover if the pin
+		// itself turns out to be wrong.
+		fs::read(&path).unwrap_or_else(|err| panic!("Failed to read {CHMLIB_TARBALL_ENV_VAR} at {path}: {err}"))
+	} else {
+		let bytes = fetch_chmlib_tarball();
+		verify_chmlib_tarball(&bytes);
+		bytes
+	};
 	let decompressor = BzDecoder::new(Cursor::new(&bytes[..]));
 	let mut archive = Archive::new(decompressor);
 	archive.unpack(out_dir).expect("Failed to extract chmlib");
 }
 
+fn fetch_chmlib_tarball() -> Vec<u8> {
+	let max_attempts = env_var_or(CHMLIB_MAX_ATTEMPTS_ENV_VAR, DEFAULT_CHMLIB_MAX_ATTEMPTS).max(1);
+	let timeout_secs = env_var_or(CHMLIB_TIMEOUT_SECS_ENV_VAR, DEFAULT_CHMLIB_TIMEOUT_SECS);
+	let client =
+		reqwest::blocking::Client::builder().timeout(Duration::from_secs(timeout_secs)).build().unwrap_or_default();
+	let mut last_error = None;
+	for attempt in 0..max_attempts {
+		match client.get(CHMLIB_TARBALL_URL).send() {
+			Ok(response) if response.status().is_success() => {
+				return response.bytes().expect("Failed to read chmlib tarball").to_vec();
+			}
+			Ok(response) => last_error = Some(format!("HTTP {}", response.status())),
+			Err(err) => last_error = Some(err.to_string()),
+		}
+		if attempt + 1 < max_attempts {
+			thread::sleep(retry_backoff_delay(attempt));
+		}
+	}
+	panic!("Failed to download chmlib after {max_attempts} attempts: {}", last_error.unwrap_or_default());
+}
+
+fn retry_backoff_delay(attempt: u32) -> Duration {
+	let millis = RETRY_BASE_DELAY_MS.saturating_mul(1u64 << attempt.min(2));
+	Duration::from_millis(millis.min(RETRY_MAX_DELAY_MS))
+}
+
+fn env_var_or<T: std::str::FromStr>(var: &str, default: T) -> T {
+	env::var(var).ok().and_then(|value| value.parse().ok()).unwrap_or(default)
+}
+
+fn verify_chmlib_tarball(bytes: &[u8]) {
+	let mut hasher = Sha256::new();
+	hasher.update(bytes);
+	let digest = format!("{:x}", hasher.finalize());
+	assert!(
+		digest == CHMLIB_TARBALL_SHA256,
+		"chmlib tarball checksum mismatch: expected {CHMLIB_TARBALL_SHA256}, got {digest}. \
+		 The download may be corrupted or tampered with; retry the build, or set {CHMLIB_TARBALL_ENV_VAR} \
+		 to the path of a local chmlib-0.40.tar.bz2 to use it directly (this check does not apply to that path)."
+	);
+}
+
 fn apply_patches(src_dir: &Path) {
 	let chm_lib_path = src_dir.join("chm_lib.c");
 	let mut contents = fs::read_to_string(&chm_lib_path).expect("Failed to read chm_lib.c");
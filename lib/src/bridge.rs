@@ -33,6 +33,7 @@ pub mod ffi {
 		pub text: String,
 		pub reference: String,
 		pub level: i32,
+		pub column: i32,
 	}
 
 	pub struct FfiTocItem {
@@ -183,6 +184,7 @@ fn parse_document(file_path: &str, password: &str) -> Result<ffi::FfiDocument, S
 				text: m.text,
 				reference: m.reference,
 				level: m.level,
+				column: m.column,
 			})
 			.collect(),
 		toc_items,
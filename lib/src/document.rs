@@ -18,6 +18,15 @@ pub enum MarkerType {
 	Link,
 	List,
 	ListItem,
+	StyleSpan,
+	Table,
+	TableRow,
+	TableCell,
+	NoteRef,
+	Note,
+	/// A lexed source-code token; the token's kind name lives in `Marker::reference` and its
+	/// numeric id in `Marker::level` (see `parser::code`).
+	SyntaxToken,
 }
 
 impl MarkerType {
@@ -36,6 +45,13 @@ impl MarkerType {
 			Self::Link => 9,
 			Self::List => 10,
 			Self::ListItem => 11,
+			Self::StyleSpan => 12,
+			Self::Table => 13,
+			Self::TableRow => 14,
+			Self::TableCell => 15,
+			Self::NoteRef => 16,
+			Self::Note => 17,
+			Self::SyntaxToken => 18,
 		}
 	}
 
@@ -54,6 +70,13 @@ impl MarkerType {
 			9 => Some(Self::Link),
 			10 => Some(Self::List),
 			11 => Some(Self::ListItem),
+			12 => Some(Self::StyleSpan),
+			13 => Some(Self::Table),
+			14 => Some(Self::TableRow),
+			15 => Some(Self::TableCell),
+			16 => Some(Self::NoteRef),
+			17 => Some(Self::Note),
+			18 => Some(Self::SyntaxToken),
 			_ => None,
 		}
 	}
@@ -66,12 +89,13 @@ pub struct Marker {
 	pub text: String,
 	pub reference: String,
 	pub level: i32,
+	pub column: i32,
 }
 
 impl Marker {
 	#[must_use]
 	pub const fn new(marker_type: MarkerType, position: usize) -> Self {
-		Self { marker_type, position, text: String::new(), reference: String::new(), level: 0 }
+		Self { marker_type, position, text: String::new(), reference: String::new(), level: 0, column: 0 }
 	}
 
 	#[must_use]
@@ -91,6 +115,12 @@ impl Marker {
 		self.level = level;
 		self
 	}
+
+	#[must_use]
+	pub const fn with_column(mut self, column: i32) -> Self {
+		self.column = column;
+		self
+	}
 }
 
 #[derive(Debug, Clone)]
@@ -169,6 +199,38 @@ impl DocumentStats {
 	}
 }
 
+/// The owner-granted permission bits from a PDF's (or similarly encrypted format's) security
+/// handler, decoded per the standard PDF permission bit layout (PDF 32000-1:2008 Table 22) so
+/// consumers can warn users or disable copy/export instead of discovering the restriction only
+/// when the action silently produces nothing useful.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DocumentPermissions {
+	pub can_print: bool,
+	pub can_print_high_quality: bool,
+	pub can_modify: bool,
+	pub can_copy: bool,
+	pub can_annotate: bool,
+	pub can_fill_forms: bool,
+	pub can_extract_for_accessibility: bool,
+	pub can_assemble_document: bool,
+}
+
+impl DocumentPermissions {
+	#[must_use]
+	pub const fn from_bits(bits: u32) -> Self {
+		Self {
+			can_print: bits & (1 << 2) != 0,
+			can_modify: bits & (1 << 3) != 0,
+			can_copy: bits & (1 << 4) != 0,
+			can_annotate: bits & (1 << 5) != 0,
+			can_fill_forms: bits & (1 << 8) != 0,
+			can_extract_for_accessibility: bits & (1 << 9) != 0,
+			can_assemble_document: bits & (1 << 10) != 0,
+			can_print_high_quality: bits & (1 << 11) != 0,
+		}
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct Document {
 	pub title: String,
@@ -176,9 +238,21 @@ pub struct Document {
 	pub buffer: DocumentBuffer,
 	pub toc_items: Vec<TocItem>,
 	pub id_positions: HashMap<String, usize>,
+	/// `#hashtag`/`[[wiki link]]` tokens found in plain-prose documents, mapping each token to
+	/// every position it occurs at (unlike `id_positions`, a tag may appear more than once).
+	pub tags: HashMap<String, Vec<usize>>,
 	pub spine_items: Vec<String>,
 	pub manifest_items: HashMap<String, String>,
+	pub metadata: HashMap<String, Vec<String>>,
+	pub notes: HashMap<String, String>,
 	pub stats: DocumentStats,
+	/// Whether the source file carried a security handler at all, regardless of whether a password
+	/// was needed to open it (an "owner password only" PDF decrypts with no prompt but still
+	/// restricts what `permissions` allows).
+	pub encrypted: bool,
+	/// The owner-granted permission bits, decoded from the security handler. `None` for
+	/// unencrypted documents, where every operation is implicitly allowed.
+	pub permissions: Option<DocumentPermissions>,
 }
 
 impl Document {
@@ -190,9 +264,14 @@ impl Document {
 			buffer: DocumentBuffer::new(),
 			toc_items: Vec::new(),
 			id_positions: HashMap::new(),
+			tags: HashMap::new(),
 			spine_items: Vec::new(),
 			manifest_items: HashMap::new(),
+			metadata: HashMap::new(),
+			notes: HashMap::new(),
 			stats: DocumentStats::default(),
+			encrypted: false,
+			permissions: None,
 		}
 	}
 
@@ -223,6 +302,17 @@ impl Default for Document {
 	}
 }
 
+bitflags! {
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	pub struct TextStyle: u32 {
+		const NONE = 0;
+		const BOLD = 1 << 0;
+		const ITALIC = 1 << 1;
+		const UNDERLINE = 1 << 2;
+		const STRIKETHROUGH = 1 << 3;
+	}
+}
+
 bitflags! {
 	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 	pub struct ParserFlags: u32 {
@@ -231,6 +321,9 @@ bitflags! {
 		const SUPPORTS_TOC = 1 << 1;
 		const SUPPORTS_PAGES = 1 << 2;
 		const SUPPORTS_LISTS = 1 << 3;
+		const SUPPORTS_HIGHLIGHT = 1 << 4;
+		const SUPPORTS_AUTOLINK = 1 << 5;
+		const SUPPORTS_OCR = 1 << 6;
 	}
 }
 
@@ -238,12 +331,22 @@ bitflags! {
 pub struct ParserContext {
 	pub file_path: String,
 	pub password: Option<String>,
+	/// Raw document bytes for callers that have the file in memory (a ZIP entry, a network
+	/// download) and want to skip spilling it to disk first. `file_path` is still used for
+	/// extension dispatch and title fallback even when this is set.
+	pub bytes: Option<Vec<u8>>,
+	/// Whether a parser advertising `ParserFlags::SUPPORTS_OCR` is allowed to fall back to OCR for
+	/// image-only pages. Defaults to `true`; callers that don't want to pay the rendering/OCR-engine
+	/// cost can opt out at runtime. Callers that can't link the OCR backend at all (no `libtesseract`
+	/// on the build host) should instead build without the `ocr` Cargo feature, which drops
+	/// `ParserFlags::SUPPORTS_OCR` from `PdfParser::supported_flags` entirely.
+	pub enable_ocr: bool,
 }
 
 impl ParserContext {
 	#[must_use]
 	pub const fn new(file_path: String) -> Self {
-		Self { file_path, password: None }
+		Self { file_path, password: None, bytes: None, enable_ocr: true }
 	}
 
 	#[must_use]
@@ -251,4 +354,16 @@ impl ParserContext {
 		self.password = Some(password);
 		self
 	}
+
+	#[must_use]
+	pub fn with_bytes(mut self, bytes: Vec<u8>) -> Self {
+		self.bytes = Some(bytes);
+		self
+	}
+
+	#[must_use]
+	pub const fn with_ocr_enabled(mut self, enable_ocr: bool) -> Self {
+		self.enable_ocr = enable_ocr;
+		self
+	}
 }
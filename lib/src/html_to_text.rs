@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+
+use scraper::{ElementRef, Html, Node};
+
+use crate::{document::TextStyle, utils::text::display_len};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HtmlSourceMode {
+	NativeHtml,
+	XhtmlFragment,
+}
+
+#[derive(Debug, Clone)]
+pub struct HeadingInfo {
+	pub offset: usize,
+	pub level: i32,
+	pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct LinkInfo {
+	pub offset: usize,
+	pub text: String,
+	pub reference: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListInfo {
+	pub offset: usize,
+	pub item_count: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListItemInfo {
+	pub offset: usize,
+	pub text: String,
+	pub level: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct StyleSpanInfo {
+	pub offset: usize,
+	pub flags: TextStyle,
+}
+
+#[derive(Debug, Clone)]
+pub struct TableInfo {
+	pub offset: usize,
+	pub row_count: i32,
+	pub col_count: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct TableRowInfo {
+	pub offset: usize,
+	pub row_index: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct TableCellInfo {
+	pub offset: usize,
+	pub row_index: i32,
+	pub col_index: i32,
+	pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NoteRefInfo {
+	pub offset: usize,
+	pub reference: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NoteInfo {
+	pub id: String,
+	pub text: String,
+}
+
+pub struct HtmlToText {
+	text: String,
+	headings: Vec<HeadingInfo>,
+	links: Vec<LinkInfo>,
+	lists: Vec<ListInfo>,
+	list_items: Vec<ListItemInfo>,
+	style_spans: Vec<StyleSpanInfo>,
+	tables: Vec<TableInfo>,
+	table_rows: Vec<TableRowInfo>,
+	table_cells: Vec<TableCellInfo>,
+	note_refs: Vec<NoteRefInfo>,
+	notes: Vec<NoteInfo>,
+	id_positions: HashMap<String, usize>,
+	current_style: TextStyle,
+}
+
+impl HtmlToText {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			text: String::new(),
+			headings: Vec::new(),
+			links: Vec::new(),
+			lists: Vec::new(),
+			list_items: Vec::new(),
+			style_spans: Vec::new(),
+			tables: Vec::new(),
+			table_rows: Vec::new(),
+			table_cells: Vec::new(),
+			note_refs: Vec::new(),
+			notes: Vec::new(),
+			id_positions: HashMap::new(),
+			current_style: TextStyle::NONE,
+		}
+	}
+
+	pub fn convert(&mut self, content: &str, _mode: HtmlSourceMode) -> bool {
+		if content.trim().is_empty() {
+			return false;
+		}
+		let document = Html::parse_document(content);
+		self.walk_children(document.root_element());
+		self.finish_section();
+		true
+	}
+
+	#[must_use]
+	pub fn get_text(&self) -> String {
+		self.text.clone()
+	}
+
+	#[must_use]
+	pub fn get_headings(&self) -> &[HeadingInfo] {
+		&self.headings
+	}
+
+	#[must_use]
+	pub fn get_links(&self) -> &[LinkInfo] {
+		&self.links
+	}
+
+	#[must_use]
+	pub fn get_lists(&self) -> &[ListInfo] {
+		&self.lists
+	}
+
+	#[must_use]
+	pub fn get_list_items(&self) -> &[ListItemInfo] {
+		&self.list_items
+	}
+
+	#[must_use]
+	pub fn get_style_spans(&self) -> &[StyleSpanInfo] {
+		&self.style_spans
+	}
+
+	#[must_use]
+	pub fn get_id_positions(&self) -> &HashMap<String, usize> {
+		&self.id_positions
+	}
+
+	#[must_use]
+	pub fn get_tables(&self) -> &[TableInfo] {
+		&self.tables
+	}
+
+	#[must_use]
+	pub fn get_table_rows(&self) -> &[TableRowInfo] {
+		&self.table_rows
+	}
+
+	#[must_use]
+	pub fn get_table_cells(&self) -> &[TableCellInfo] {
+		&self.table_cells
+	}
+
+	#[must_use]
+	pub fn get_note_refs(&self) -> &[NoteRefInfo] {
+		&self.note_refs
+	}
+
+	#[must_use]
+	pub fn get_notes(&self) -> &[NoteInfo] {
+		&self.notes
+	}
+
+	/// The current write position in `self.text`, in UTF-16 code units - the same unit
+	/// `DocumentBuffer` tracks, so offsets recorded here still line up once a caller adds its own
+	/// UTF-16 `section_start` to them (see `epub::build_content_markers`).
+	fn position(&self) -> usize {
+		display_len(&self.text)
+	}
+
+	fn push_style(&mut self, flags: TextStyle) {
+		if flags.is_empty() {
+			return;
+		}
+		let new_style = self.current_style | flags;
+		if new_style != self.current_style {
+			self.current_style = new_style;
+			self.style_spans.push(StyleSpanInfo { offset: self.position(), flags: self.current_style });
+		}
+	}
+
+	fn pop_style(&mut self, flags: TextStyle, previous: TextStyle) {
+		if flags.is_empty() {
+			return;
+		}
+		if previous != self.current_style {
+			self.current_style = previous;
+			self.style_spans.push(StyleSpanInfo { offset: self.position(), flags: self.current_style });
+		}
+	}
+
+	fn finish_section(&mut self) {
+		if self.current_style != TextStyle::NONE {
+			self.current_style = TextStyle::NONE;
+			self.style_spans.push(StyleSpanInfo { offset: self.position(), flags: TextStyle::NONE });
+		}
+	}
+
+	fn walk_children(&mut self, element: ElementRef) {
+		for child in element.children() {
+			self.walk_node(child);
+		}
+	}
+
+	fn walk_node(&mut self, node: ego_tree::NodeRef<Node>) {
+		match node.value() {
+			Node::Text(text) => {
+				self.text.push_str(text);
+			}
+			Node::Element(el) => {
+				let Some(element) = ElementRef::wrap(node) else { return };
+				let tag_name = el.name();
+				let epub_type = el.attr("epub:type");
+				if matches!(epub_type, Some("footnote") | Some("endnote") | Some("rearnote")) {
+					if let Some(note_id) = el.attr("id") {
+						let note_text = element.text().collect::<String>().trim().to_string();
+						self.notes.push(NoteInfo { id: note_id.to_string(), text: note_text });
+					}
+					return;
+				}
+				if let Some(id) = el.attr("id") {
+					self.id_positions.insert(id.to_string(), self.position());
+				}
+				let style_flags = style_flags_for_tag(tag_name);
+				let previous_style = self.current_style;
+				self.push_style(style_flags);
+				match tag_name {
+					"h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+						let level = tag_name[1..].parse::<i32>().unwrap_or(1);
+						let byte_offset = self.text.len();
+						let offset = self.position();
+						self.walk_children(element);
+						let heading_text = self.text[byte_offset..].trim().to_string();
+						if !heading_text.is_empty() {
+							self.headings.push(HeadingInfo { offset, level, text: heading_text });
+						}
+						self.ensure_trailing_newline();
+					}
+					"a" => {
+						let reference = el.attr("href").unwrap_or("").to_string();
+						let byte_offset = self.text.len();
+						let offset = self.position();
+						self.walk_children(element);
+						let link_text = self.text[byte_offset..].to_string();
+						if epub_type == Some("noteref") {
+							if !reference.is_empty() {
+								self.note_refs.push(NoteRefInfo { offset, reference });
+							}
+						} else if !reference.is_empty() && !link_text.is_empty() {
+							self.links.push(LinkInfo { offset, text: link_text, reference });
+						}
+					}
+					"ul" | "ol" => {
+						let list_offset = self.position();
+						let item_count = element.children().filter(|c| is_element_named(*c, "li")).count();
+						self.lists.push(ListInfo { offset: list_offset, item_count: item_count as i32 });
+						self.walk_children(element);
+						self.ensure_trailing_newline();
+					}
+					"li" => {
+						let byte_offset = self.text.len();
+						let offset = self.position();
+						self.walk_children(element);
+						let item_text = self.text[byte_offset..].trim().to_string();
+						self.list_items.push(ListItemInfo { offset, text: item_text, level: 1 });
+						self.ensure_trailing_newline();
+					}
+					"table" => {
+						self.process_table(element);
+					}
+					"br" => {
+						self.text.push('\n');
+					}
+					"p" | "div" | "section" | "article" | "blockquote" => {
+						self.walk_children(element);
+						self.ensure_trailing_newline();
+					}
+					_ => {
+						self.walk_children(element);
+					}
+				}
+				self.pop_style(style_flags, previous_style);
+			}
+			_ => {}
+		}
+	}
+
+	fn ensure_trailing_newline(&mut self) {
+		if !self.text.ends_with('\n') {
+			self.text.push('\n');
+		}
+	}
+
+	fn process_table(&mut self, table: ElementRef) {
+		let rows = collect_table_rows(table);
+		let row_count = rows.len();
+		let col_count = rows.iter().map(|row| collect_table_cells(*row).len()).max().unwrap_or(0);
+		let table_offset = self.position();
+		self.tables.push(TableInfo { offset: table_offset, row_count: row_count as i32, col_count: col_count as i32 });
+		for (row_index, row) in rows.iter().enumerate() {
+			let row_offset = self.position();
+			self.table_rows.push(TableRowInfo { offset: row_offset, row_index: row_index as i32 });
+			let cells = collect_table_cells(*row);
+			let cell_count = cells.len();
+			for (col_index, cell) in cells.iter().enumerate() {
+				let byte_offset = self.text.len();
+				let offset = self.position();
+				self.walk_children(*cell);
+				let cell_text = self.text[byte_offset..].trim().to_string();
+				self.table_cells.push(TableCellInfo {
+					offset,
+					row_index: row_index as i32,
+					col_index: col_index as i32,
+					text: cell_text,
+				});
+				if col_index + 1 < cell_count {
+					self.text.push('\t');
+				}
+			}
+			self.ensure_trailing_newline();
+		}
+		self.ensure_trailing_newline();
+	}
+}
+
+fn collect_table_rows(element: ElementRef) -> Vec<ElementRef> {
+	let mut rows = Vec::new();
+	for child in element.children() {
+		let Some(child_element) = ElementRef::wrap(child) else { continue };
+		match child_element.value().name() {
+			"tr" => rows.push(child_element),
+			"thead" | "tbody" | "tfoot" => rows.extend(collect_table_rows(child_element)),
+			_ => {}
+		}
+	}
+	rows
+}
+
+fn collect_table_cells(row: ElementRef) -> Vec<ElementRef> {
+	row.children()
+		.filter_map(ElementRef::wrap)
+		.filter(|child| matches!(child.value().name(), "td" | "th"))
+		.collect()
+}
+
+impl Default for HtmlToText {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+fn is_element_named(node: ego_tree::NodeRef<Node>, name: &str) -> bool {
+	matches!(node.value(), Node::Element(el) if el.name() == name)
+}
+
+fn style_flags_for_tag(tag_name: &str) -> TextStyle {
+	match tag_name {
+		"b" | "strong" => TextStyle::BOLD,
+		"i" | "em" => TextStyle::ITALIC,
+		"u" => TextStyle::UNDERLINE,
+		"s" | "strike" | "del" => TextStyle::STRIKETHROUGH,
+		_ => TextStyle::NONE,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_basic_conversion() {
+		let mut converter = HtmlToText::new();
+		assert!(converter.convert("<html><body><h1>Title</h1><p>Hello <b>world</b></p></body></html>", HtmlSourceMode::NativeHtml));
+		assert!(converter.get_text().contains("Hello"));
+		assert_eq!(converter.get_headings().len(), 1);
+		assert_eq!(converter.get_headings()[0].text, "Title");
+	}
+
+	#[test]
+	fn test_style_spans_emit_bold_transition() {
+		let mut converter = HtmlToText::new();
+		converter.convert("<p>plain <b>bold</b> plain</p>", HtmlSourceMode::NativeHtml);
+		let spans = converter.get_style_spans();
+		assert_eq!(spans.len(), 2);
+		assert_eq!(spans[0].flags, TextStyle::BOLD);
+		assert_eq!(spans[1].flags, TextStyle::NONE);
+	}
+
+	#[test]
+	fn test_links_and_lists() {
+		let mut converter = HtmlToText::new();
+		converter.convert("<ul><li><a href=\"#a\">One</a></li><li>Two</li></ul>", HtmlSourceMode::NativeHtml);
+		assert_eq!(converter.get_lists().len(), 1);
+		assert_eq!(converter.get_lists()[0].item_count, 2);
+		assert_eq!(converter.get_list_items().len(), 2);
+		assert_eq!(converter.get_links().len(), 1);
+		assert_eq!(converter.get_links()[0].reference, "#a");
+	}
+
+	#[test]
+	fn test_table_extraction() {
+		let mut converter = HtmlToText::new();
+		converter.convert(
+			"<table><tr><td>A1</td><td>B1</td></tr><tr><td>A2</td><td>B2</td></tr></table>",
+			HtmlSourceMode::NativeHtml,
+		);
+		assert_eq!(converter.get_tables().len(), 1);
+		assert_eq!(converter.get_tables()[0].row_count, 2);
+		assert_eq!(converter.get_tables()[0].col_count, 2);
+		assert_eq!(converter.get_table_rows().len(), 2);
+		assert_eq!(converter.get_table_cells().len(), 4);
+		assert_eq!(converter.get_table_cells()[2].row_index, 1);
+		assert_eq!(converter.get_table_cells()[2].col_index, 0);
+		assert_eq!(converter.get_table_cells()[2].text, "A2");
+	}
+}
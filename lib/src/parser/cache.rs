@@ -0,0 +1,120 @@
+use std::{
+	collections::{HashMap, VecDeque, hash_map::DefaultHasher},
+	hash::{Hash, Hasher},
+	path::PathBuf,
+	sync::Mutex,
+	time::UNIX_EPOCH,
+};
+
+use crate::document::{Document, ParserContext};
+
+const DEFAULT_MAX_CAPACITY: usize = 32;
+
+/// Identifies a parsed document by the inputs that would change its content: the file's canonical
+/// path, modification time and length (so edits/replacements invalidate the entry), the password
+/// used to decrypt it, if any, and whether OCR fallback was enabled for the parse.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+	canonical_path: PathBuf,
+	mtime_nanos: u128,
+	len: u64,
+	password_hash: u64,
+	enable_ocr: bool,
+}
+
+struct CacheState {
+	entries: HashMap<CacheKey, Document>,
+	/// Oldest-to-newest access order, for LRU eviction once `max_capacity` is exceeded.
+	order: VecDeque<CacheKey>,
+	max_capacity: usize,
+}
+
+impl CacheState {
+	const fn new(max_capacity: usize) -> Self {
+		Self { entries: HashMap::new(), order: VecDeque::new(), max_capacity }
+	}
+}
+
+static CACHE: Mutex<Option<CacheState>> = Mutex::new(None);
+
+fn with_cache<R>(f: impl FnOnce(&mut CacheState) -> R) -> R {
+	let mut guard = CACHE.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+	let state = guard.get_or_insert_with(|| CacheState::new(DEFAULT_MAX_CAPACITY));
+	f(state)
+}
+
+/// Returns a cached `Document` for `context` if the backing file hasn't changed since it was
+/// cached, cloning it out (the FFI layer clones `Document` out of the parser anyway).
+pub fn get(context: &ParserContext) -> Option<Document> {
+	let key = cache_key(context)?;
+	with_cache(|state| {
+		let document = state.entries.get(&key)?.clone();
+		touch(state, &key);
+		Some(document)
+	})
+}
+
+pub fn insert(context: &ParserContext, document: Document) {
+	let Some(key) = cache_key(context) else { return };
+	with_cache(|state| {
+		if !state.entries.contains_key(&key) {
+			state.order.push_back(key.clone());
+		}
+		state.entries.insert(key.clone(), document);
+		evict_if_needed(state);
+	});
+}
+
+pub fn clear() {
+	with_cache(|state| {
+		state.entries.clear();
+		state.order.clear();
+	});
+}
+
+pub fn set_max_capacity(max_capacity: usize) {
+	with_cache(|state| {
+		state.max_capacity = max_capacity.max(1);
+		evict_if_needed(state);
+	});
+}
+
+fn touch(state: &mut CacheState, key: &CacheKey) {
+	if let Some(index) = state.order.iter().position(|existing| existing == key) {
+		if let Some(key) = state.order.remove(index) {
+			state.order.push_back(key);
+		}
+	}
+}
+
+fn evict_if_needed(state: &mut CacheState) {
+	while state.entries.len() > state.max_capacity {
+		let Some(oldest) = state.order.pop_front() else { break };
+		state.entries.remove(&oldest);
+	}
+}
+
+/// Returns `None` (cache bypass) when the context carries in-memory `bytes`: those bytes may have
+/// no relation to the content currently on disk at `file_path`, so there is no on-disk signal we
+/// could key on that would reliably invalidate the entry.
+fn cache_key(context: &ParserContext) -> Option<CacheKey> {
+	if context.bytes.is_some() {
+		return None;
+	}
+	let canonical_path = std::fs::canonicalize(&context.file_path).ok()?;
+	let metadata = std::fs::metadata(&canonical_path).ok()?;
+	let mtime_nanos = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+	Some(CacheKey {
+		canonical_path,
+		mtime_nanos,
+		len: metadata.len(),
+		password_hash: hash_password(context.password.as_deref()),
+		enable_ocr: context.enable_ocr,
+	})
+}
+
+fn hash_password(password: Option<&str>) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	password.hash(&mut hasher);
+	hasher.finish()
+}
@@ -0,0 +1,329 @@
+use std::{collections::HashMap, fs, path::Path, sync::OnceLock};
+
+use anyhow::{Context, Result};
+
+use crate::{
+	document::{Document, DocumentBuffer, Marker, MarkerType, ParserContext, ParserFlags},
+	parser::Parser,
+	utils::{encoding::convert_to_utf8, text::display_len},
+};
+
+pub struct CodeParser;
+
+impl Parser for CodeParser {
+	fn name(&self) -> &str {
+		"Source Code"
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["rs", "py", "js", "ts", "jsx", "tsx", "c", "h", "cpp", "hpp", "cc", "go", "json", "java", "rb", "sh"]
+	}
+
+	fn supported_flags(&self) -> ParserFlags {
+		ParserFlags::SUPPORTS_HIGHLIGHT
+	}
+
+	fn parse(&self, context: &ParserContext) -> Result<Document> {
+		let bytes = fs::read(&context.file_path)
+			.with_context(|| format!("Failed to read source file '{}'", context.file_path))?;
+		if bytes.is_empty() {
+			anyhow::bail!("Source file is empty: {}", context.file_path);
+		}
+		let text = convert_to_utf8(&bytes);
+		let extension =
+			Path::new(&context.file_path).extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+		let title =
+			Path::new(&context.file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled").to_string();
+		let mut buffer = DocumentBuffer::with_content(text.clone());
+		if let Some(syntax) = syntax_set().get(extension.as_str()) {
+			for marker in tokenize(&text, syntax) {
+				buffer.add_marker(marker);
+			}
+		}
+		let mut document = Document::new().with_title(title);
+		document.set_buffer(buffer);
+		Ok(document)
+	}
+}
+
+/// A minimal `syntect`-style description of a language's lexical rules: keywords, comment
+/// delimiters and string-quote characters. Good enough to tag the token kinds consumers need to
+/// colorize code without shipping a full grammar/scope stack.
+struct LanguageSyntax {
+	keywords: &'static [&'static str],
+	line_comment: Option<&'static str>,
+	block_comment: Option<(&'static str, &'static str)>,
+	string_quotes: &'static [char],
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+	Keyword,
+	String,
+	Comment,
+	Number,
+}
+
+impl TokenKind {
+	const fn id(self) -> i32 {
+		match self {
+			Self::Keyword => 0,
+			Self::String => 1,
+			Self::Comment => 2,
+			Self::Number => 3,
+		}
+	}
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::Keyword => "keyword",
+			Self::String => "string",
+			Self::Comment => "comment",
+			Self::Number => "number",
+		}
+	}
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+	"as", "break", "const", "continue", "crate", "else", "enum", "extern", "fn", "for", "if", "impl", "in", "let",
+	"loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+	"trait", "true", "false", "type", "unsafe", "use", "where", "while", "async", "await", "dyn",
+];
+const PYTHON_KEYWORDS: &[&str] = &[
+	"and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else", "except",
+	"finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass",
+	"raise", "return", "try", "while", "with", "yield", "None", "True", "False",
+];
+const JS_KEYWORDS: &[&str] = &[
+	"break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do", "else", "export",
+	"extends", "finally", "for", "function", "if", "import", "in", "instanceof", "let", "new", "return", "super",
+	"switch", "this", "throw", "try", "typeof", "var", "void", "while", "with", "yield", "async", "await", "null",
+	"true", "false", "undefined",
+];
+const C_KEYWORDS: &[&str] = &[
+	"auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else", "enum", "extern",
+	"float", "for", "goto", "if", "inline", "int", "long", "register", "return", "short", "signed", "sizeof",
+	"static", "struct", "switch", "typedef", "union", "unsigned", "void", "volatile", "while", "class", "namespace",
+	"public", "private", "protected", "template", "this", "new", "delete", "virtual", "bool", "true", "false",
+];
+const GO_KEYWORDS: &[&str] = &[
+	"break", "case", "chan", "const", "continue", "default", "defer", "else", "fallthrough", "for", "func", "go",
+	"goto", "if", "import", "interface", "map", "package", "range", "return", "select", "struct", "switch", "type",
+	"var", "true", "false", "nil",
+];
+const JAVA_KEYWORDS: &[&str] = &[
+	"abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "continue", "default", "do",
+	"double", "else", "enum", "extends", "final", "finally", "float", "for", "if", "implements", "import",
+	"instanceof", "int", "interface", "long", "native", "new", "package", "private", "protected", "public", "return",
+	"short", "static", "super", "switch", "synchronized", "this", "throw", "throws", "transient", "try", "void",
+	"volatile", "while", "true", "false", "null",
+];
+const RUBY_KEYWORDS: &[&str] = &[
+	"begin", "break", "case", "class", "def", "defined?", "do", "else", "elsif", "end", "ensure", "false", "for",
+	"if", "in", "module", "next", "nil", "not", "or", "redo", "rescue", "retry", "return", "self", "super", "then",
+	"true", "undef", "unless", "until", "when", "while", "yield",
+];
+const SHELL_KEYWORDS: &[&str] = &[
+	"if", "then", "else", "elif", "fi", "for", "in", "do", "done", "while", "until", "case", "esac", "function",
+	"return", "local", "export", "readonly",
+];
+const JSON_KEYWORDS: &[&str] = &["true", "false", "null"];
+
+fn syntax_set() -> &'static HashMap<&'static str, LanguageSyntax> {
+	static SYNTAX_SET: OnceLock<HashMap<&'static str, LanguageSyntax>> = OnceLock::new();
+	SYNTAX_SET.get_or_init(|| {
+		let mut set = HashMap::new();
+		set.insert(
+			"rs",
+			LanguageSyntax {
+				keywords: RUST_KEYWORDS,
+				line_comment: Some("//"),
+				block_comment: Some(("/*", "*/")),
+				string_quotes: &['"'],
+			},
+		);
+		set.insert(
+			"py",
+			LanguageSyntax {
+				keywords: PYTHON_KEYWORDS,
+				line_comment: Some("#"),
+				block_comment: None,
+				string_quotes: &['"', '\''],
+			},
+		);
+		for ext in ["js", "ts", "jsx", "tsx"] {
+			set.insert(
+				ext,
+				LanguageSyntax {
+					keywords: JS_KEYWORDS,
+					line_comment: Some("//"),
+					block_comment: Some(("/*", "*/")),
+					string_quotes: &['"', '\''],
+				},
+			);
+		}
+		for ext in ["c", "h", "cpp", "hpp", "cc"] {
+			set.insert(
+				ext,
+				LanguageSyntax {
+					keywords: C_KEYWORDS,
+					line_comment: Some("//"),
+					block_comment: Some(("/*", "*/")),
+					string_quotes: &['"', '\''],
+				},
+			);
+		}
+		set.insert(
+			"go",
+			LanguageSyntax {
+				keywords: GO_KEYWORDS,
+				line_comment: Some("//"),
+				block_comment: Some(("/*", "*/")),
+				string_quotes: &['"', '`'],
+			},
+		);
+		set.insert(
+			"java",
+			LanguageSyntax {
+				keywords: JAVA_KEYWORDS,
+				line_comment: Some("//"),
+				block_comment: Some(("/*", "*/")),
+				string_quotes: &['"'],
+			},
+		);
+		set.insert(
+			"rb",
+			LanguageSyntax { keywords: RUBY_KEYWORDS, line_comment: Some("#"), block_comment: None, string_quotes: &['"', '\''] },
+		);
+		set.insert(
+			"sh",
+			LanguageSyntax {
+				keywords: SHELL_KEYWORDS,
+				line_comment: Some("#"),
+				block_comment: None,
+				string_quotes: &['"', '\''],
+			},
+		);
+		set.insert(
+			"json",
+			LanguageSyntax { keywords: JSON_KEYWORDS, line_comment: None, block_comment: None, string_quotes: &['"'] },
+		);
+		set
+	})
+}
+
+/// Walks `text` once, tracking a small amount of state (in a block comment, in a string) to yield
+/// `SyntaxToken` markers for keywords, string literals, comments and numbers. Positions are in
+/// `DocumentBuffer`'s UTF-16 code-unit units, matching every other marker-producing parser.
+fn tokenize(text: &str, syntax: &LanguageSyntax) -> Vec<Marker> {
+	let mut markers = Vec::new();
+	let chars: Vec<char> = text.chars().collect();
+	let mut pos = 0usize; // display-length position of chars[i]
+	let mut i = 0usize;
+	while i < chars.len() {
+		if let Some((open, _)) = syntax.block_comment {
+			if starts_with_at(&chars, i, open) {
+				emit_block_comment(&chars, &mut i, &mut pos, syntax, &mut markers);
+				continue;
+			}
+		}
+		if let Some(line_comment) = syntax.line_comment {
+			if starts_with_at(&chars, i, line_comment) {
+				let start_pos = pos;
+				let start_i = i;
+				while i < chars.len() && chars[i] != '\n' {
+					i += 1;
+				}
+				let token: String = chars[start_i..i].iter().collect();
+				pos += display_len(&token);
+				markers.push(syntax_marker(TokenKind::Comment, start_pos, token));
+				continue;
+			}
+		}
+		if syntax.string_quotes.contains(&chars[i]) {
+			let quote = chars[i];
+			let start_pos = pos;
+			let start_i = i;
+			i += 1;
+			pos += 1;
+			while i < chars.len() && chars[i] != quote {
+				if chars[i] == '\\' && i + 1 < chars.len() {
+					i += 1;
+					pos += display_len(&chars[i - 1].to_string());
+				}
+				pos += display_len(&chars[i].to_string());
+				i += 1;
+			}
+			if i < chars.len() {
+				i += 1;
+				pos += 1;
+			}
+			let token: String = chars[start_i..i].iter().collect();
+			markers.push(syntax_marker(TokenKind::String, start_pos, token));
+			continue;
+		}
+		if chars[i].is_ascii_digit() {
+			let start_pos = pos;
+			let start_i = i;
+			while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
+				i += 1;
+			}
+			let token: String = chars[start_i..i].iter().collect();
+			pos += display_len(&token);
+			markers.push(syntax_marker(TokenKind::Number, start_pos, token));
+			continue;
+		}
+		if chars[i].is_alphabetic() || chars[i] == '_' {
+			let start_pos = pos;
+			let start_i = i;
+			while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '?') {
+				i += 1;
+			}
+			let token: String = chars[start_i..i].iter().collect();
+			let token_display_len = display_len(&token);
+			if syntax.keywords.contains(&token.as_str()) {
+				markers.push(syntax_marker(TokenKind::Keyword, start_pos, token));
+			}
+			pos += token_display_len;
+			continue;
+		}
+		pos += display_len(&chars[i].to_string());
+		i += 1;
+	}
+	markers
+}
+
+fn syntax_marker(kind: TokenKind, position: usize, text: String) -> Marker {
+	Marker::new(MarkerType::SyntaxToken, position)
+		.with_text(text)
+		.with_level(kind.id())
+		.with_reference(kind.name().to_string())
+}
+
+fn starts_with_at(chars: &[char], index: usize, needle: &str) -> bool {
+	let needle_chars: Vec<char> = needle.chars().collect();
+	if needle_chars.is_empty() || index + needle_chars.len() > chars.len() {
+		return false;
+	}
+	chars[index..index + needle_chars.len()] == needle_chars[..]
+}
+
+/// Consumes a whole block comment, from its opening delimiter (at `*i`, not yet consumed) through
+/// its closing delimiter or end-of-file, emitting one `Comment` marker spanning it.
+fn emit_block_comment(chars: &[char], i: &mut usize, pos: &mut usize, syntax: &LanguageSyntax, markers: &mut Vec<Marker>) {
+	let Some((_, close)) = syntax.block_comment else { return };
+	let start_i = *i;
+	let start_pos = *pos;
+	while *i < chars.len() && !starts_with_at(chars, *i, close) {
+		*pos += display_len(&chars[*i].to_string());
+		*i += 1;
+	}
+	if *i < chars.len() {
+		let close_len = close.chars().count();
+		let close_text: String = chars[*i..*i + close_len].iter().collect();
+		*i += close_len;
+		*pos += display_len(&close_text);
+	}
+	let token: String = chars[start_i..*i].iter().collect();
+	markers.push(syntax_marker(TokenKind::Comment, start_pos, token));
+}
@@ -10,7 +10,7 @@ use roxmltree::{Document as XmlDocument, Node, NodeType};
 use zip::ZipArchive;
 
 use crate::{
-	document::{Document, DocumentBuffer, Marker, MarkerType, ParserContext, ParserFlags},
+	document::{Document, DocumentBuffer, Marker, MarkerType, ParserContext, ParserFlags, TextStyle},
 	html_to_text::HeadingInfo,
 	parser::{Parser, utils::build_toc_from_buffer},
 };
@@ -97,6 +97,10 @@ fn traverse(
 			process_paragraph(node, buffer, headings, id_positions, rels);
 			return;
 		}
+		if tag_name == "tbl" {
+			process_table(node, buffer);
+			return;
+		}
 	}
 	for child in node.children() {
 		traverse(child, buffer, headings, id_positions, rels);
@@ -114,6 +118,7 @@ fn process_paragraph(
 	let mut paragraph_text = String::new();
 	let mut heading_level = 0;
 	let mut is_paragraph_style_heading = false;
+	let mut current_style = TextStyle::NONE;
 	for child in element.children() {
 		if child.node_type() != NodeType::Element {
 			continue;
@@ -131,10 +136,18 @@ fn process_paragraph(
 		} else if tag_name == "hyperlink" {
 			process_hyperlink(child, &mut paragraph_text, buffer, rels, paragraph_start);
 		} else if tag_name == "r" {
-			if heading_level == 0 {
-				if let Some(rpr_node) = find_child_by_name(child, "rPr") {
+			if let Some(rpr_node) = find_child_by_name(child, "rPr") {
+				if heading_level == 0 {
 					heading_level = get_run_heading_level(rpr_node);
 				}
+				let run_style = get_run_style_flags(rpr_node);
+				if run_style != current_style {
+					current_style = run_style;
+					buffer.add_marker(
+						Marker::new(MarkerType::StyleSpan, paragraph_start + paragraph_text.len())
+							.with_level(current_style.bits() as i32),
+					);
+				}
 			}
 			if let Some(instr_text_node) = find_child_by_name(child, "instrText") {
 				if let Some(instruction) = instr_text_node.text() {
@@ -158,6 +171,11 @@ fn process_paragraph(
 			paragraph_text.push_str(&get_run_text(child));
 		}
 	}
+	if current_style != TextStyle::NONE {
+		buffer.add_marker(
+			Marker::new(MarkerType::StyleSpan, paragraph_start + paragraph_text.len()).with_level(TextStyle::NONE.bits() as i32),
+		);
+	}
 	let trimmed = paragraph_text.trim();
 	buffer.append(trimmed);
 	buffer.append("\n");
@@ -215,6 +233,58 @@ fn process_hyperlink(
 	}
 }
 
+fn process_table(table_element: Node, buffer: &mut DocumentBuffer) {
+	let rows: Vec<Node> =
+		table_element.children().filter(|child| child.node_type() == NodeType::Element && child.tag_name().name() == "tr").collect();
+	let row_count = rows.len();
+	let col_count = rows.iter().map(|row| table_cells(*row).len()).max().unwrap_or(0);
+	let table_start = buffer.current_position();
+	buffer.add_marker(
+		Marker::new(MarkerType::Table, table_start).with_level(row_count as i32).with_column(col_count as i32),
+	);
+	for (row_index, row) in rows.iter().enumerate() {
+		let row_start = buffer.current_position();
+		buffer.add_marker(Marker::new(MarkerType::TableRow, row_start).with_level(row_index as i32));
+		let cells = table_cells(*row);
+		let cell_count = cells.len();
+		for (col_index, cell) in cells.iter().enumerate() {
+			let cell_start = buffer.current_position();
+			let cell_text = table_cell_text(*cell);
+			buffer.add_marker(
+				Marker::new(MarkerType::TableCell, cell_start)
+					.with_text(cell_text.clone())
+					.with_level(row_index as i32)
+					.with_column(col_index as i32),
+			);
+			buffer.append(&cell_text);
+			if col_index + 1 < cell_count {
+				buffer.append("\t");
+			}
+		}
+		buffer.append("\n");
+	}
+	buffer.append("\n");
+}
+
+fn table_cells(row_element: Node) -> Vec<Node> {
+	row_element.children().filter(|child| child.node_type() == NodeType::Element && child.tag_name().name() == "tc").collect()
+}
+
+fn table_cell_text(cell_element: Node) -> String {
+	let paragraphs: Vec<String> = cell_element
+		.children()
+		.filter(|child| child.node_type() == NodeType::Element && child.tag_name().name() == "p")
+		.map(|paragraph| {
+			paragraph
+				.children()
+				.filter(|child| child.node_type() == NodeType::Element && child.tag_name().name() == "r")
+				.map(get_run_text)
+				.collect::<String>()
+		})
+		.collect();
+	paragraphs.join("\n")
+}
+
 fn get_paragraph_heading_level(pr_element: Node) -> i32 {
 	const MAX_HEADING_LEVEL: i32 = 9;
 	for child in pr_element.children() {
@@ -264,6 +334,35 @@ fn get_run_heading_level(rpr_element: Node) -> i32 {
 	0
 }
 
+fn get_run_style_flags(rpr_element: Node) -> TextStyle {
+	let mut flags = TextStyle::NONE;
+	if toggle_property_enabled(rpr_element, "b") {
+		flags |= TextStyle::BOLD;
+	}
+	if toggle_property_enabled(rpr_element, "i") {
+		flags |= TextStyle::ITALIC;
+	}
+	if let Some(node) = find_child_by_name(rpr_element, "u") {
+		if node.attribute("val") != Some("none") {
+			flags |= TextStyle::UNDERLINE;
+		}
+	}
+	if toggle_property_enabled(rpr_element, "strike") {
+		flags |= TextStyle::STRIKETHROUGH;
+	}
+	flags
+}
+
+fn toggle_property_enabled(rpr_element: Node, tag_name: &str) -> bool {
+	let Some(node) = find_child_by_name(rpr_element, tag_name) else {
+		return false;
+	};
+	match node.attribute("val") {
+		None => true,
+		Some(val) => !matches!(val, "false" | "0" | "off"),
+	}
+}
+
 fn get_run_text(run_element: Node) -> String {
 	let mut text = String::new();
 	for child in run_element.children() {
@@ -1,5 +1,7 @@
 use std::{
 	collections::HashMap,
+	fs::File,
+	io::BufReader,
 	path::{Component, Path, PathBuf},
 };
 
@@ -7,7 +9,10 @@ use epub::doc::{EpubDoc, NavPoint};
 
 use crate::{
 	document::{Document, DocumentBuffer, Marker, MarkerType, ParserContext, ParserFlags, TocItem},
-	html_to_text::{HeadingInfo, HtmlSourceMode, HtmlToText, LinkInfo, ListInfo, ListItemInfo},
+	html_to_text::{
+		HeadingInfo, HtmlSourceMode, HtmlToText, LinkInfo, ListInfo, ListItemInfo, NoteInfo, NoteRefInfo, StyleSpanInfo,
+		TableCellInfo, TableInfo, TableRowInfo,
+	},
 	parser::Parser,
 	utils::text::trim_string,
 	xml_to_text::XmlToText,
@@ -19,6 +24,12 @@ struct SectionContent {
 	links: Vec<LinkInfo>,
 	lists: Vec<ListInfo>,
 	list_items: Vec<ListItemInfo>,
+	style_spans: Vec<StyleSpanInfo>,
+	tables: Vec<TableInfo>,
+	table_rows: Vec<TableRowInfo>,
+	table_cells: Vec<TableCellInfo>,
+	note_refs: Vec<NoteRefInfo>,
+	notes: Vec<NoteInfo>,
 	id_positions: HashMap<String, usize>,
 }
 
@@ -47,6 +58,7 @@ impl Parser for EpubParser {
 		let mut epub = EpubDoc::new(&context.file_path).map_err(|e| format!("Failed to open EPUB '{}': {e}", context.file_path))?;
 		let mut buffer = DocumentBuffer::new();
 		let mut id_positions = HashMap::new();
+		let mut notes = HashMap::new();
 		let mut sections = Vec::new();
 		let manifest_items: HashMap<String, String> = epub
 			.resources
@@ -74,36 +86,25 @@ impl Parser for EpubParser {
 			};
 			let section_path = normalize_path(&resource_path);
 			let section_start = buffer.current_position();
-			let section_label = format!("Section {}", index + 1);
-			buffer.add_marker(Marker::new(MarkerType::SectionBreak, section_start).with_text(section_label));
 			match convert_section(&content) {
 				Ok(section) => {
-					for (id, relative) in section.id_positions {
-						id_positions.insert(id, section_start + relative);
+					for note in &section.notes {
+						notes.insert(note.id.clone(), note.text.clone());
 					}
-					for heading in section.headings {
-						let marker_type = heading_marker_type(heading.level);
-						buffer.add_marker(
-							Marker::new(marker_type, section_start + heading.offset).with_text(heading.text.clone()).with_level(heading.level),
-						);
+					let is_notes_only = !section.notes.is_empty() && section.text.trim().is_empty();
+					if is_notes_only {
+						for (id, relative) in section.id_positions {
+							id_positions.insert(id, section_start + relative);
+						}
+						continue;
 					}
-					for link in section.links {
-						let resolved = resolve_href(&section_path, &link.reference);
-						buffer.add_marker(
-							Marker::new(MarkerType::Link, section_start + link.offset)
-								.with_text(link.text.clone())
-								.with_reference(resolved),
-						);
+					let section_label = format!("Section {}", index + 1);
+					buffer.add_marker(Marker::new(MarkerType::SectionBreak, section_start).with_text(section_label));
+					for (id, relative) in &section.id_positions {
+						id_positions.insert(id.clone(), section_start + relative);
 					}
-					for list in section.lists {
-						buffer.add_marker(Marker::new(MarkerType::List, section_start + list.offset).with_level(list.item_count));
-					}
-					for list_item in section.list_items {
-						buffer.add_marker(
-							Marker::new(MarkerType::ListItem, section_start + list_item.offset)
-								.with_text(list_item.text.clone())
-								.with_level(list_item.level),
-						);
+					for marker in build_content_markers(section_start, &section_path, &section) {
+						buffer.add_marker(marker);
 					}
 					buffer.append(&section.text);
 					if !buffer.content.ends_with('\n') {
@@ -140,12 +141,160 @@ impl Parser for EpubParser {
 		document.id_positions = id_positions;
 		document.spine_items = epub.spine.iter().map(|item| item.idref.clone()).collect();
 		document.manifest_items = manifest_items;
+		document.notes = notes;
+		for key in DUBLIN_CORE_KEYS {
+			let values: Vec<String> = epub
+				.metadata
+				.get(*key)
+				.map(|entries| entries.iter().map(|entry| trim_string(&entry.value)).filter(|value| !value.is_empty()).collect())
+				.unwrap_or_default();
+			if !values.is_empty() {
+				document.metadata.insert((*key).to_string(), values);
+			}
+		}
 		document.toc_items = toc_items;
 		document.compute_stats();
 		Ok(document)
 	}
 }
 
+struct LazySection {
+	idref: String,
+	path: String,
+	spine_index: usize,
+}
+
+struct CachedSection {
+	text: String,
+	markers: Vec<Marker>,
+	id_positions: HashMap<String, usize>,
+}
+
+/// Opens an EPUB without eagerly converting every spine item, for callers (e.g. a
+/// terminal reader) that only need one chapter's text and markers at a time. Each
+/// section is converted and cached on first access; markers and `id_positions` are
+/// relative to that section's own text, since sections may be loaded out of order and
+/// an absolute position across the whole book isn't known until every prior section has
+/// also been loaded.
+pub struct LazyEpubDocument {
+	epub: EpubDoc<BufReader<File>>,
+	pub title: String,
+	pub author: String,
+	pub metadata: HashMap<String, Vec<String>>,
+	pub manifest_items: HashMap<String, String>,
+	sections: Vec<LazySection>,
+	cache: HashMap<usize, CachedSection>,
+}
+
+impl LazyEpubDocument {
+	pub fn open(context: &ParserContext) -> Result<Self, String> {
+		let epub = EpubDoc::new(&context.file_path).map_err(|e| format!("Failed to open EPUB '{}': {e}", context.file_path))?;
+		let manifest_items: HashMap<String, String> =
+			epub.resources.iter().map(|(id, item)| (id.clone(), normalize_path(&item.path))).collect();
+		let mut sections = Vec::new();
+		for (spine_index, item) in epub.spine.iter().enumerate() {
+			let Some(resource) = epub.resources.get(&item.idref) else {
+				continue;
+			};
+			if !is_textual_mime(&resource.mime) {
+				continue;
+			}
+			sections.push(LazySection { idref: item.idref.clone(), path: normalize_path(&resource.path), spine_index });
+		}
+		if sections.is_empty() {
+			return Err("EPUB has no readable content (no readable spine items)".to_string());
+		}
+		let title = epub.get_title().filter(|t| !t.trim().is_empty()).unwrap_or_else(|| fallback_title(&context.file_path));
+		let author =
+			epub.mdata("creator").map(|item| trim_string(&item.value)).filter(|s| !s.is_empty()).unwrap_or_default();
+		let mut metadata = HashMap::new();
+		for key in DUBLIN_CORE_KEYS {
+			let values: Vec<String> = epub
+				.metadata
+				.get(*key)
+				.map(|entries| entries.iter().map(|entry| trim_string(&entry.value)).filter(|value| !value.is_empty()).collect())
+				.unwrap_or_default();
+			if !values.is_empty() {
+				metadata.insert((*key).to_string(), values);
+			}
+		}
+		Ok(Self { epub, title, author, metadata, manifest_items, sections, cache: HashMap::new() })
+	}
+
+	#[must_use]
+	pub fn section_count(&self) -> usize {
+		self.sections.len()
+	}
+
+	#[must_use]
+	pub fn section_path(&self, index: usize) -> Option<&str> {
+		self.sections.get(index).map(|section| section.path.as_str())
+	}
+
+	#[must_use]
+	pub fn section_label(&self, index: usize) -> Option<String> {
+		self.sections.get(index).map(|section| format!("Section {}", section.spine_index + 1))
+	}
+
+	pub fn section_text(&mut self, index: usize) -> Result<&str, String> {
+		self.ensure_loaded(index)?;
+		Ok(&self.cache[&index].text)
+	}
+
+	pub fn section_markers(&mut self, index: usize) -> Result<&[Marker], String> {
+		self.ensure_loaded(index)?;
+		Ok(&self.cache[&index].markers)
+	}
+
+	pub fn section_id_position(&mut self, index: usize, id: &str) -> Result<Option<usize>, String> {
+		self.ensure_loaded(index)?;
+		Ok(self.cache[&index].id_positions.get(id).copied())
+	}
+
+	/// Resolves a TOC/link href (`path#fragment`) to the section that contains it and
+	/// the offset of the fragment within that section's own text, loading only that one
+	/// section rather than the whole book.
+	pub fn resolve_reference(&mut self, reference: &str) -> Result<Option<(usize, usize)>, String> {
+		let (path_part, fragment) = split_href(reference);
+		let Some(index) = self.sections.iter().position(|section| section.path == path_part) else {
+			return Ok(None);
+		};
+		self.ensure_loaded(index)?;
+		let offset = fragment.and_then(|frag| self.cache[&index].id_positions.get(&frag).copied()).unwrap_or(0);
+		Ok(Some((index, offset)))
+	}
+
+	/// Sums the cached text length of sections `0..index`. Returns `None` if any of
+	/// those sections haven't been loaded yet, since an absolute position can't be
+	/// computed without knowing every preceding section's length.
+	#[must_use]
+	pub fn section_absolute_offset(&self, index: usize) -> Option<usize> {
+		let mut offset = 0;
+		for i in 0..index {
+			offset += crate::utils::text::display_len(&self.cache.get(&i)?.text);
+		}
+		Some(offset)
+	}
+
+	fn ensure_loaded(&mut self, index: usize) -> Result<(), String> {
+		if self.cache.contains_key(&index) {
+			return Ok(());
+		}
+		let section = self.sections.get(index).ok_or_else(|| format!("section index {index} out of range"))?;
+		let idref = section.idref.clone();
+		let path = section.path.clone();
+		let (content, _) =
+			self.epub.get_resource_str(&idref).ok_or_else(|| format!("failed to read spine item '{idref}'"))?;
+		let converted = convert_section(&content)?;
+		let markers = build_content_markers(0, &path, &converted);
+		self.cache.insert(index, CachedSection { text: converted.text, markers, id_positions: converted.id_positions });
+		Ok(())
+	}
+}
+
+const DUBLIN_CORE_KEYS: &[&str] =
+	&["publisher", "language", "identifier", "date", "description", "subject", "rights", "contributor"];
+
 fn fallback_title(path: &str) -> String {
 	Path::new(path)
 		.file_stem()
@@ -163,6 +312,12 @@ fn convert_section(content: &str) -> Result<SectionContent, String> {
 			links: xml_converter.get_links().to_vec(),
 			lists: xml_converter.get_lists().to_vec(),
 			list_items: xml_converter.get_list_items().to_vec(),
+			style_spans: xml_converter.get_style_spans().to_vec(),
+			tables: xml_converter.get_tables().to_vec(),
+			table_rows: xml_converter.get_table_rows().to_vec(),
+			table_cells: xml_converter.get_table_cells().to_vec(),
+			note_refs: xml_converter.get_note_refs().to_vec(),
+			notes: xml_converter.get_notes().to_vec(),
 			id_positions: xml_converter.get_id_positions().clone(),
 		});
 	}
@@ -174,12 +329,66 @@ fn convert_section(content: &str) -> Result<SectionContent, String> {
 			links: html_converter.get_links().to_vec(),
 			lists: html_converter.get_lists().to_vec(),
 			list_items: html_converter.get_list_items().to_vec(),
+			style_spans: html_converter.get_style_spans().to_vec(),
+			tables: html_converter.get_tables().to_vec(),
+			table_rows: html_converter.get_table_rows().to_vec(),
+			table_cells: html_converter.get_table_cells().to_vec(),
+			note_refs: html_converter.get_note_refs().to_vec(),
+			notes: html_converter.get_notes().to_vec(),
 			id_positions: html_converter.get_id_positions().clone(),
 		});
 	}
 	Err("unsupported content".into())
 }
 
+fn build_content_markers(section_start: usize, section_path: &str, section: &SectionContent) -> Vec<Marker> {
+	let mut markers = Vec::new();
+	for heading in &section.headings {
+		let marker_type = heading_marker_type(heading.level);
+		markers.push(Marker::new(marker_type, section_start + heading.offset).with_text(heading.text.clone()).with_level(heading.level));
+	}
+	for link in &section.links {
+		let resolved = resolve_href(section_path, &link.reference);
+		markers.push(
+			Marker::new(MarkerType::Link, section_start + link.offset).with_text(link.text.clone()).with_reference(resolved),
+		);
+	}
+	for list in &section.lists {
+		markers.push(Marker::new(MarkerType::List, section_start + list.offset).with_level(list.item_count));
+	}
+	for list_item in &section.list_items {
+		markers.push(
+			Marker::new(MarkerType::ListItem, section_start + list_item.offset)
+				.with_text(list_item.text.clone())
+				.with_level(list_item.level),
+		);
+	}
+	for span in &section.style_spans {
+		markers.push(Marker::new(MarkerType::StyleSpan, section_start + span.offset).with_level(span.flags.bits() as i32));
+	}
+	for table in &section.tables {
+		markers.push(
+			Marker::new(MarkerType::Table, section_start + table.offset).with_level(table.row_count).with_column(table.col_count),
+		);
+	}
+	for row in &section.table_rows {
+		markers.push(Marker::new(MarkerType::TableRow, section_start + row.offset).with_level(row.row_index));
+	}
+	for cell in &section.table_cells {
+		markers.push(
+			Marker::new(MarkerType::TableCell, section_start + cell.offset)
+				.with_text(cell.text.clone())
+				.with_level(cell.row_index)
+				.with_column(cell.col_index),
+		);
+	}
+	for note_ref in &section.note_refs {
+		let resolved = resolve_href(section_path, &note_ref.reference);
+		markers.push(Marker::new(MarkerType::NoteRef, section_start + note_ref.offset).with_reference(resolved));
+	}
+	markers
+}
+
 fn heading_marker_type(level: i32) -> MarkerType {
 	match level {
 		1 => MarkerType::Heading1,
@@ -1,16 +1,38 @@
 use std::{collections::HashMap, fs};
 
 use anyhow::{Context, Result};
-use roxmltree::{Document as XmlDocument, Node, NodeType};
+use quick_xml::{Reader, events::Event};
 
 use crate::{
 	document::{Document, DocumentBuffer, Marker, MarkerType, ParserContext, ParserFlags},
 	parser::Parser,
-	xml_to_text::XmlToText,
+	utils::text::display_len,
 };
 
 pub struct Fb2Parser;
 
+struct HeadingData {
+	offset: usize,
+	level: i32,
+	text: String,
+}
+
+struct LinkData {
+	offset: usize,
+	text: String,
+	reference: String,
+}
+
+struct ParsedFb2 {
+	text: String,
+	title: String,
+	author: String,
+	headings: Vec<HeadingData>,
+	section_offsets: Vec<usize>,
+	links: Vec<LinkData>,
+	id_positions: HashMap<String, usize>,
+}
+
 impl Parser for Fb2Parser {
 	fn name(&self) -> &str {
 		"FictionBook Documents"
@@ -25,24 +47,16 @@ impl Parser for Fb2Parser {
 	}
 
 	fn parse(&self, context: &ParserContext) -> Result<Document> {
-		let mut xml_content = fs::read_to_string(&context.file_path)
+		let xml_content = fs::read_to_string(&context.file_path)
 			.with_context(|| format!("Failed to read FB2 file '{}'", context.file_path))?;
 		if xml_content.is_empty() {
 			anyhow::bail!("FB2 file is empty");
 		}
-		const CLOSING_TAG: &str = "</FictionBook>";
-		if let Some(pos) = xml_content.rfind(CLOSING_TAG) {
-			xml_content.truncate(pos + CLOSING_TAG.len());
-		}
-		xml_content = remove_binary_elements(&xml_content).unwrap_or(xml_content);
-		let (title, author) = extract_metadata(&xml_content);
-		let mut converter = XmlToText::new();
-		if !converter.convert(&xml_content) {
-			anyhow::bail!("Failed to convert FB2 XML to text");
-		}
+		let parsed = stream_parse_fb2(&xml_content)
+			.with_context(|| format!("Failed to parse FB2 file '{}'", context.file_path))?;
 		let mut buffer = DocumentBuffer::new();
-		buffer.append(&converter.get_text());
-		for heading in converter.get_headings() {
+		buffer.append(&parsed.text);
+		for heading in &parsed.headings {
 			let marker_type = match heading.level {
 				1 => MarkerType::Heading1,
 				2 => MarkerType::Heading2,
@@ -55,162 +69,201 @@ impl Parser for Fb2Parser {
 				Marker::new(marker_type, heading.offset).with_text(heading.text.clone()).with_level(heading.level),
 			);
 		}
-		for offset in converter.get_section_offsets() {
+		for offset in &parsed.section_offsets {
 			buffer.add_marker(Marker::new(MarkerType::SectionBreak, *offset));
 		}
-		for link in converter.get_links() {
+		for link in &parsed.links {
 			buffer.add_marker(
 				Marker::new(MarkerType::Link, link.offset)
 					.with_text(link.text.clone())
 					.with_reference(link.reference.clone()),
 			);
 		}
-		let id_positions: HashMap<String, usize> = converter.get_id_positions().clone();
-		let mut document = Document::new().with_title(title).with_author(author);
+		let mut document = Document::new().with_title(parsed.title).with_author(parsed.author);
 		document.set_buffer(buffer);
-		document.id_positions = id_positions;
+		document.id_positions = parsed.id_positions;
 		Ok(document)
 	}
 }
 
-fn remove_binary_elements(xml_content: &str) -> Option<String> {
-	let doc = XmlDocument::parse(xml_content).ok()?;
-	let mut result = String::new();
-	serialize_without_binary(doc.root(), &mut result);
-	Some(result)
-}
+/// Converts an FB2 document to plain text in a single `quick-xml` pull-parser pass, tracking an
+/// explicit element-name stack for context instead of building a DOM. `<binary>` subtrees (large
+/// base64 cover images/attachments) are skipped without buffering their text, which is what makes
+/// this safe for big files that `roxmltree` would otherwise load entirely into memory.
+fn stream_parse_fb2(xml: &str) -> Result<ParsedFb2> {
+	let mut reader = Reader::from_str(xml);
+	reader.config_mut().trim_text(false);
+	let mut buf = Vec::new();
+	let mut stack: Vec<String> = Vec::new();
+	let mut binary_depth: usize = 0;
 
-fn serialize_without_binary(node: Node, output: &mut String) {
-	match node.node_type() {
-		NodeType::Root => {
-			for child in node.children() {
-				serialize_without_binary(child, output);
-			}
-		}
-		NodeType::Element => {
-			let tag_name = node.tag_name().name();
-			if tag_name == "binary" {
-				return;
-			}
-			output.push('<');
-			output.push_str(tag_name);
-			for attr in node.attributes() {
-				output.push(' ');
-				output.push_str(attr.name());
-				output.push_str("=\"");
-				output.push_str(&escape_xml(attr.value()));
-				output.push('"');
-			}
-			if node.children().count() == 0 {
-				output.push_str("/>");
-			} else {
-				output.push('>');
-				for child in node.children() {
-					serialize_without_binary(child, output);
+	let mut text = String::new();
+	let mut pos = 0usize;
+	let mut headings = Vec::new();
+	let mut section_offsets = Vec::new();
+	let mut links = Vec::new();
+	let mut id_positions = HashMap::new();
+	let mut section_depth: i32 = 0;
+
+	let mut title = String::new();
+	let mut author_first = String::new();
+	let mut author_last = String::new();
+
+	let mut title_offset = 0usize;
+	let mut title_text = String::new();
+	let mut title_depth: usize = 0;
+	let mut link_offset = 0usize;
+	let mut link_text = String::new();
+	let mut link_href = String::new();
+	let mut link_depth: usize = 0;
+
+	loop {
+		match reader.read_event_into(&mut buf).context("Malformed FB2 XML")? {
+			Event::Eof => break,
+			Event::Start(e) => {
+				let name = tag_local_name(&e);
+				if binary_depth > 0 {
+					binary_depth += 1;
+					stack.push(name);
+					continue;
 				}
-				output.push_str("</");
-				output.push_str(tag_name);
-				output.push('>');
+				if name == "binary" {
+					binary_depth = 1;
+					stack.push(name);
+					continue;
+				}
+				if name == "section" {
+					section_offsets.push(pos);
+					section_depth += 1;
+				}
+				if name == "title" {
+					if title_depth == 0 {
+						title_offset = pos;
+						title_text.clear();
+					}
+					title_depth += 1;
+				}
+				if name == "a" {
+					if link_depth == 0 {
+						link_offset = pos;
+						link_text.clear();
+						link_href = find_attr(&e, b"href")
+							.or_else(|| find_attr(&e, b"l:href"))
+							.or_else(|| find_attr(&e, b"xlink:href"))
+							.unwrap_or_default();
+					}
+					link_depth += 1;
+				}
+				if let Some(id) = find_attr(&e, b"id") {
+					id_positions.insert(id, pos);
+				}
+				stack.push(name);
 			}
-		}
-		NodeType::Text => {
-			if let Some(text) = node.text() {
-				output.push_str(&escape_xml(text));
+			Event::Empty(e) => {
+				if binary_depth == 0 {
+					let name = tag_local_name(&e);
+					if name == "empty-line" {
+						push_text(&mut text, &mut pos, "\n");
+					}
+					if let Some(id) = find_attr(&e, b"id") {
+						id_positions.insert(id, pos);
+					}
+				}
 			}
-		}
-		NodeType::Comment => {
-			if let Some(text) = node.text() {
-				output.push_str("<!--");
-				output.push_str(text);
-				output.push_str("-->");
+			Event::End(e) => {
+				let name = tag_local_name(&e);
+				if binary_depth > 0 {
+					if name == "binary" {
+						binary_depth = 0;
+					}
+					stack.pop();
+					continue;
+				}
+				match name.as_str() {
+					"title" => {
+						title_depth = title_depth.saturating_sub(1);
+						if title_depth == 0 {
+							let level = heading_level_for_section_depth(section_depth);
+							let heading_text = title_text.trim().to_string();
+							if !heading_text.is_empty() {
+								headings.push(HeadingData { offset: title_offset, level, text: heading_text });
+							}
+							ensure_trailing_newline(&mut text, &mut pos);
+						}
+					}
+					"section" => {
+						section_depth -= 1;
+						ensure_trailing_newline(&mut text, &mut pos);
+					}
+					"a" => {
+						link_depth = link_depth.saturating_sub(1);
+						if link_depth == 0 && !link_href.is_empty() && !link_text.is_empty() {
+							links.push(LinkData { offset: link_offset, text: link_text.clone(), reference: link_href.clone() });
+						}
+					}
+					"p" | "empty-line" => ensure_trailing_newline(&mut text, &mut pos),
+					_ => {}
+				}
+				stack.pop();
 			}
-		}
-		NodeType::PI => {
-			if let Some(text) = node.text() {
-				output.push_str("<?");
-				output.push_str(text);
-				output.push_str("?>");
+			Event::Text(e) => {
+				if binary_depth > 0 {
+					continue;
+				}
+				let content = e.unescape().unwrap_or_default().to_string();
+				if stack_ends_with(&stack, &["title-info", "book-title"]) {
+					title.push_str(&content);
+				} else if stack_ends_with(&stack, &["title-info", "author", "first-name"]) {
+					author_first.push_str(&content);
+				} else if stack_ends_with(&stack, &["title-info", "author", "last-name"]) {
+					author_last.push_str(&content);
+				}
+				if title_depth > 0 {
+					title_text.push_str(&content);
+				}
+				if link_depth > 0 {
+					link_text.push_str(&content);
+				}
+				push_text(&mut text, &mut pos, &content);
 			}
+			_ => {}
 		}
+		buf.clear();
 	}
+	let author =
+		[author_first.trim(), author_last.trim()].into_iter().filter(|s| !s.is_empty()).collect::<Vec<_>>().join(" ");
+	Ok(ParsedFb2 { text, title: title.trim().to_string(), author, headings, section_offsets, links, id_positions })
 }
 
-fn escape_xml(s: &str) -> String {
-	s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+fn push_text(text: &mut String, pos: &mut usize, content: &str) {
+	text.push_str(content);
+	*pos += display_len(content);
 }
 
-fn extract_metadata(xml_content: &str) -> (String, String) {
-	let doc = match XmlDocument::parse(xml_content) {
-		Ok(d) => d,
-		Err(_) => return (String::new(), String::new()),
-	};
-	let mut title = String::new();
-	let mut author = String::new();
-	if let Some(title_node) =
-		find_element_by_path(doc.root(), &["FictionBook", "description", "title-info", "book-title"])
-	{
-		title = get_element_text_content(title_node).trim().to_string();
-	}
-	if let Some(author_node) = find_element_by_path(doc.root(), &["FictionBook", "description", "title-info", "author"]) {
-		let first_name =
-			find_child_by_name(author_node, "first-name").map(get_element_text_content).unwrap_or_default();
-		let last_name = find_child_by_name(author_node, "last-name").map(get_element_text_content).unwrap_or_default();
-		if !first_name.is_empty() {
-			author.push_str(&first_name);
-		}
-		if !last_name.is_empty() {
-			if !author.is_empty() {
-				author.push(' ');
-			}
-			author.push_str(&last_name);
-		}
-		author = author.trim().to_string();
+fn ensure_trailing_newline(text: &mut String, pos: &mut usize) {
+	if !text.ends_with('\n') {
+		push_text(text, pos, "\n");
 	}
-	(title, author)
 }
 
-fn find_element_by_path<'a, 'input>(node: Node<'a, 'input>, path: &[&str]) -> Option<Node<'a, 'input>> {
-	if path.is_empty() {
-		return Some(node);
-	}
-	let target = path[0];
-	let remaining = &path[1..];
-	for child in node.children() {
-		if child.node_type() == NodeType::Element {
-			let tag_name = child.tag_name().name();
-			if tag_name == target {
-				if remaining.is_empty() {
-					return Some(child);
-				}
-				return find_element_by_path(child, remaining);
-			}
-		}
-	}
-	None
+fn heading_level_for_section_depth(section_depth: i32) -> i32 {
+	section_depth.max(1).min(6)
 }
 
-fn find_child_by_name<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
-	for child in node.children() {
-		if child.node_type() == NodeType::Element && child.tag_name().name() == name {
-			return Some(child);
-		}
+fn stack_ends_with(stack: &[String], path: &[&str]) -> bool {
+	if stack.len() < path.len() {
+		return false;
 	}
-	None
+	stack[stack.len() - path.len()..].iter().zip(path.iter()).all(|(a, b)| a.eq_ignore_ascii_case(b))
 }
 
-fn get_element_text_content(node: Node) -> String {
-	let mut text = String::new();
-	collect_text_content(node, &mut text);
-	text
+fn tag_local_name(e: &quick_xml::events::BytesStart) -> String {
+	String::from_utf8_lossy(e.local_name().as_ref()).to_string()
 }
 
-fn collect_text_content(node: Node, text: &mut String) {
-	if node.node_type() == NodeType::Text {
-		if let Some(t) = node.text() {
-			text.push_str(t);
-		}
-	}
-	for child in node.children() {
-		collect_text_content(child, text);
-	}
+fn find_attr(e: &quick_xml::events::BytesStart, key: &[u8]) -> Option<String> {
+	e.attributes()
+		.filter_map(std::result::Result::ok)
+		.find(|a| a.key.as_ref() == key)
+		.map(|a| String::from_utf8_lossy(&a.value).to_string())
 }
@@ -1,11 +1,11 @@
 use std::{collections::HashMap, fs, path::Path};
 
-use pulldown_cmark::{Event, HeadingLevel, Tag, TagEnd};
+use pulldown_cmark::{Event, HeadingLevel, Options, Tag, TagEnd};
 
 use crate::{
 	document::{Document, DocumentBuffer, Marker, MarkerType, ParserContext, ParserFlags, TocItem},
 	parser::Parser,
-	utils::encoding::convert_to_utf8,
+	utils::{encoding::convert_to_utf8, text::display_len},
 };
 
 pub struct MarkdownParser;
@@ -16,6 +16,34 @@ struct HeadingInfo {
 	position: usize,
 }
 
+struct ListInfo {
+	position: usize,
+	item_count: i32,
+}
+
+struct ListItemInfo {
+	position: usize,
+	text: String,
+	level: i32,
+}
+
+struct MarkdownContent {
+	text: String,
+	headings: Vec<HeadingInfo>,
+	links: Vec<(usize, (String, String))>,
+	lists: Vec<ListInfo>,
+	list_items: Vec<ListItemInfo>,
+	section_breaks: Vec<usize>,
+	footnote_refs: Vec<(usize, String)>,
+	id_positions: HashMap<String, usize>,
+	first_h1_title: Option<String>,
+}
+
+struct OpenList {
+	position: usize,
+	item_count: i32,
+}
+
 impl Parser for MarkdownParser {
 	fn name(&self) -> &str {
 		"Markdown Files"
@@ -26,7 +54,7 @@ impl Parser for MarkdownParser {
 	}
 
 	fn supported_flags(&self) -> ParserFlags {
-		ParserFlags::SUPPORTS_TOC
+		ParserFlags::SUPPORTS_TOC | ParserFlags::SUPPORTS_LISTS
 	}
 
 	fn parse(&self, context: &ParserContext) -> Result<Document, String> {
@@ -36,12 +64,13 @@ impl Parser for MarkdownParser {
 			return Err(format!("Markdown file is empty: {}", context.file_path));
 		}
 		let markdown_content = convert_to_utf8(&bytes);
-		let (text, headings, links, id_positions) = parse_markdown_to_text(&markdown_content)?;
-		// Extract title from filename
-		let title =
-			Path::new(&context.file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled").to_string();
-		let mut buffer = DocumentBuffer::with_content(text);
-		for heading in headings.iter() {
+		let content = parse_markdown_to_text(&markdown_content)?;
+		// Prefer the first H1 as the title; most Markdown filenames are slugs, not titles.
+		let title = content.first_h1_title.clone().unwrap_or_else(|| {
+			Path::new(&context.file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled").to_string()
+		});
+		let mut buffer = DocumentBuffer::with_content(content.text);
+		for heading in content.headings.iter() {
 			let marker_type = match heading.level {
 				1 => MarkerType::Heading1,
 				2 => MarkerType::Heading2,
@@ -56,34 +85,93 @@ impl Parser for MarkdownParser {
 					.with_level(heading.level as i32),
 			);
 		}
-		for (position, (text, url)) in links {
+		for (position, (text, url)) in content.links {
 			buffer.add_marker(Marker::new(MarkerType::Link, position).with_text(text).with_reference(url));
 		}
-		let toc_items = build_toc_from_headings(&headings);
+		for list in content.lists {
+			buffer.add_marker(Marker::new(MarkerType::List, list.position).with_level(list.item_count));
+		}
+		for item in content.list_items {
+			buffer.add_marker(
+				Marker::new(MarkerType::ListItem, item.position).with_text(item.text).with_level(item.level),
+			);
+		}
+		for offset in content.section_breaks {
+			buffer.add_marker(Marker::new(MarkerType::SectionBreak, offset));
+		}
+		for (position, label) in content.footnote_refs {
+			buffer.add_marker(
+				Marker::new(MarkerType::Link, position)
+					.with_text(format!("[{label}]"))
+					.with_reference(format!("#fn-{label}")),
+			);
+		}
+		let toc_items = build_toc_from_headings(&content.headings);
 		let mut doc = Document::new().with_title(title);
 		doc.set_buffer(buffer);
 		doc.toc_items = toc_items;
-		doc.id_positions = id_positions;
+		doc.id_positions = content.id_positions;
 		doc.compute_stats();
 		Ok(doc)
 	}
 }
 
-fn parse_markdown_to_text(
-	markdown: &str,
-) -> Result<(String, Vec<HeadingInfo>, Vec<(usize, (String, String))>, HashMap<String, usize>), String> {
-	let parser = pulldown_cmark::Parser::new(markdown);
+fn parse_markdown_to_text(markdown: &str) -> Result<MarkdownContent, String> {
+	let options =
+		Options::ENABLE_FOOTNOTES | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH;
+	let parser = pulldown_cmark::Parser::new_ext(markdown, options);
 	let mut text = String::new();
 	let mut headings = Vec::new();
 	let mut links = Vec::new();
 	let mut id_positions = HashMap::new();
+	let mut lists = Vec::new();
+	let mut list_items = Vec::new();
+	let mut section_breaks = Vec::new();
 	let mut current_heading_level: Option<usize> = None;
 	let mut current_heading_text = String::new();
+	let mut current_heading_has_explicit_id = false;
+	let mut slug_counts: HashMap<String, usize> = HashMap::new();
 	let mut current_link_text = String::new();
 	let mut current_link_url = String::new();
 	let mut in_link = false;
+	let mut open_lists: Vec<OpenList> = Vec::new();
+	let mut item_starts: Vec<usize> = Vec::new();
+	let mut footnote_refs: Vec<(usize, String)> = Vec::new();
+	let mut footnote_defs: Vec<(String, String)> = Vec::new();
+	let mut in_table_cell = false;
+	let mut current_cell_text = String::new();
+	let mut current_row_cells: Vec<String> = Vec::new();
+	let mut first_h1_title: Option<String> = None;
+	let mut current_footnote_label: Option<String> = None;
+	let mut current_footnote_text = String::new();
 	for event in parser {
+		if let Some(label) = current_footnote_label.clone() {
+			match event {
+				Event::End(TagEnd::FootnoteDefinition) => {
+					footnote_defs.push((label, current_footnote_text.trim().to_string()));
+					current_footnote_label = None;
+					current_footnote_text.clear();
+				}
+				Event::Text(t) => current_footnote_text.push_str(&t),
+				Event::Code(c) => current_footnote_text.push_str(&c),
+				Event::SoftBreak | Event::HardBreak => current_footnote_text.push(' '),
+				Event::End(TagEnd::Paragraph) if !current_footnote_text.is_empty() => {
+					if !current_footnote_text.ends_with(' ') {
+						current_footnote_text.push(' ');
+					}
+				}
+				_ => {}
+			}
+			continue;
+		}
 		match event {
+			Event::Start(Tag::FootnoteDefinition(label)) => {
+				current_footnote_label = Some(label.to_string());
+				current_footnote_text.clear();
+			}
+			Event::FootnoteReference(label) => {
+				footnote_refs.push((display_len(&text), label.to_string()));
+			}
 			Event::Start(Tag::Heading { level, id, .. }) => {
 				let heading_level = match level {
 					HeadingLevel::H1 => 1,
@@ -95,15 +183,23 @@ fn parse_markdown_to_text(
 				};
 				current_heading_level = Some(heading_level);
 				current_heading_text.clear();
+				current_heading_has_explicit_id = id.is_some();
 				if let Some(id_str) = id {
-					id_positions.insert(id_str.to_string(), text.len());
+					id_positions.insert(id_str.to_string(), display_len(&text));
 				}
 			}
 			Event::End(TagEnd::Heading(_)) => {
 				if let Some(level) = current_heading_level {
 					let heading_text = current_heading_text.trim().to_string();
 					if !heading_text.is_empty() {
-						headings.push(HeadingInfo { text: heading_text.clone(), level, position: text.len() });
+						let position = display_len(&text);
+						headings.push(HeadingInfo { text: heading_text.clone(), level, position });
+						if !current_heading_has_explicit_id {
+							insert_heading_slug(&heading_text, position, &mut slug_counts, &mut id_positions);
+						}
+						if level == 1 && first_h1_title.is_none() {
+							first_h1_title = Some(heading_text.clone());
+						}
 						text.push_str(&heading_text);
 						text.push('\n');
 						text.push('\n');
@@ -119,7 +215,7 @@ fn parse_markdown_to_text(
 			}
 			Event::End(TagEnd::Link) => {
 				if in_link {
-					links.push((text.len(), (current_link_text.clone(), current_link_url.clone())));
+					links.push((display_len(&text), (current_link_text.clone(), current_link_url.clone())));
 					text.push_str(&current_link_text);
 					in_link = false;
 				}
@@ -130,6 +226,8 @@ fn parse_markdown_to_text(
 					current_heading_text.push_str(&content);
 				} else if in_link {
 					current_link_text.push_str(&content);
+				} else if in_table_cell {
+					current_cell_text.push_str(&content);
 				} else {
 					text.push_str(&content);
 				}
@@ -140,15 +238,56 @@ fn parse_markdown_to_text(
 					current_heading_text.push_str(&code_str);
 				} else if in_link {
 					current_link_text.push_str(&code_str);
+				} else if in_table_cell {
+					current_cell_text.push_str(&code_str);
 				} else {
 					text.push_str(&code_str);
 				}
 			}
 			Event::SoftBreak | Event::HardBreak => {
-				if current_heading_level.is_none() && !in_link {
-					text.push('\n');
+				if current_heading_level.is_some() {
+					current_heading_text.push(' ');
 				} else if in_link {
 					current_link_text.push(' ');
+				} else if in_table_cell {
+					current_cell_text.push(' ');
+				} else {
+					text.push('\n');
+				}
+			}
+			Event::TaskListMarker(checked) => {
+				let marker = if checked { "[x] " } else { "[ ] " };
+				if current_heading_level.is_some() {
+					current_heading_text.push_str(marker);
+				} else if in_link {
+					current_link_text.push_str(marker);
+				} else if in_table_cell {
+					current_cell_text.push_str(marker);
+				} else {
+					text.push_str(marker);
+				}
+			}
+			Event::Start(Tag::Table(_)) => {
+				if !text.is_empty() && !text.ends_with('\n') {
+					text.push('\n');
+				}
+			}
+			Event::End(TagEnd::Table) => {
+				text.push('\n');
+			}
+			Event::Start(Tag::TableCell) => {
+				in_table_cell = true;
+				current_cell_text.clear();
+			}
+			Event::End(TagEnd::TableCell) => {
+				in_table_cell = false;
+				current_row_cells.push(current_cell_text.trim().to_string());
+			}
+			Event::End(TagEnd::TableHead) | Event::End(TagEnd::TableRow) => {
+				if !current_row_cells.is_empty() {
+					text.push_str(&current_row_cells.join(" | "));
+					text.push('\n');
+					current_row_cells.clear();
 				}
 			}
 			Event::Start(Tag::Paragraph) => {
@@ -168,17 +307,43 @@ fn parse_markdown_to_text(
 				if !text.ends_with("\n\n") && !text.is_empty() {
 					text.push('\n');
 				}
+				// `position` is a byte offset, used only to slice the list's own text back out
+				// below; it is converted to a UTF-16 position before landing in `ListInfo`.
+				open_lists.push(OpenList { position: text.len(), item_count: 0 });
 			}
 			Event::End(TagEnd::List(_)) => {
 				if !text.ends_with('\n') {
 					text.push('\n');
 				}
+				if let Some(open_list) = open_lists.pop() {
+					let position = display_len(&text[..open_list.position]);
+					lists.push(ListInfo { position, item_count: open_list.item_count });
+				}
+			}
+			Event::Start(Tag::Item) => {
+				item_starts.push(text.len());
 			}
-			Event::Start(Tag::Item) => {}
 			Event::End(TagEnd::Item) => {
 				if !text.ends_with('\n') {
 					text.push('\n');
 				}
+				if let Some(open_list) = open_lists.last_mut() {
+					open_list.item_count += 1;
+				}
+				if let Some(item_start) = item_starts.pop() {
+					let item_text = text[item_start..].trim().to_string();
+					let position = display_len(&text[..item_start]);
+					list_items.push(ListItemInfo { position, text: item_text, level: open_lists.len() as i32 });
+				}
+			}
+			Event::Rule => {
+				if !text.ends_with("\n\n") && !text.is_empty() {
+					if !text.ends_with('\n') {
+						text.push('\n');
+					}
+					text.push('\n');
+				}
+				section_breaks.push(display_len(&text));
 			}
 			Event::Start(Tag::CodeBlock(_)) => {
 				if !text.ends_with("\n\n") && !text.is_empty() {
@@ -205,7 +370,69 @@ fn parse_markdown_to_text(
 			_ => {}
 		}
 	}
-	Ok((text, headings, links, id_positions))
+	append_footnote_definitions(&footnote_defs, &mut text, &mut id_positions);
+	Ok(MarkdownContent {
+		text,
+		headings,
+		links,
+		lists,
+		list_items,
+		section_breaks,
+		footnote_refs,
+		id_positions,
+		first_h1_title,
+	})
+}
+
+/// Appends a "Notes" section containing each footnote definition's body, recording where it
+/// landed in `id_positions` under `fn-<label>` so a `#fn-<label>` reference marker can resolve
+/// to it.
+fn append_footnote_definitions(
+	footnote_defs: &[(String, String)],
+	text: &mut String,
+	id_positions: &mut HashMap<String, usize>,
+) {
+	if footnote_defs.is_empty() {
+		return;
+	}
+	if !text.ends_with('\n') {
+		text.push('\n');
+	}
+	if !text.ends_with("\n\n") {
+		text.push('\n');
+	}
+	text.push_str("Notes\n\n");
+	for (label, body) in footnote_defs {
+		let position = display_len(text);
+		id_positions.insert(format!("fn-{label}"), position);
+		text.push_str(&format!("[{label}] {body}"));
+		text.push('\n');
+	}
+}
+
+/// Derives a GitHub-style anchor slug for a heading that has no explicit id, deduplicating
+/// against earlier headings with the same base slug by appending `-1`, `-2`, etc.
+fn insert_heading_slug(
+	heading_text: &str,
+	position: usize,
+	slug_counts: &mut HashMap<String, usize>,
+	id_positions: &mut HashMap<String, usize>,
+) {
+	let base_slug = derive_heading_slug(heading_text);
+	if base_slug.is_empty() {
+		return;
+	}
+	let count = slug_counts.entry(base_slug.clone()).or_insert(0);
+	let slug = if *count == 0 { base_slug } else { format!("{base_slug}-{count}") };
+	*count += 1;
+	id_positions.insert(slug, position);
+}
+
+fn derive_heading_slug(heading_text: &str) -> String {
+	let lowered = heading_text.to_lowercase();
+	let filtered: String =
+		lowered.chars().filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '-').collect();
+	filtered.trim().split_whitespace().collect::<Vec<_>>().join("-")
 }
 
 fn build_toc_from_headings(headings: &[HeadingInfo]) -> Vec<TocItem> {
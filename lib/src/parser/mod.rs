@@ -1,15 +1,36 @@
-use std::{collections::HashMap, sync::OnceLock};
+use std::{
+	collections::HashMap,
+	fs,
+	io::{BufReader, Read},
+	path::{Path, PathBuf},
+	sync::{
+		OnceLock,
+		atomic::{AtomicU64, Ordering},
+	},
+};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use zip::ZipArchive;
 
 use crate::document::{Document, ParserContext, ParserFlags};
 
+mod cache;
+pub mod code;
 pub mod epub;
+pub mod fb2;
 pub mod html;
 pub mod markdown;
+pub mod odt;
+pub mod org;
+pub mod pdf;
 pub mod text;
 mod utils;
 
+/// Prefix `parse` errors are tagged with when a PDF (or other encrypted format) could not be
+/// opened because it needs a password, or the one supplied was wrong, so callers can distinguish
+/// "ask the user for a password" from a generic parse failure.
+pub const PASSWORD_REQUIRED_ERROR_PREFIX: &str = "password_required: ";
+
 pub trait Parser: Send + Sync {
 	fn name(&self) -> &str;
 	fn extensions(&self) -> &[&str];
@@ -47,6 +68,13 @@ impl ParserRegistry {
 		self.parsers.values().find(|p| p.extensions().iter().any(|e| e.to_lowercase() == ext)).map(|p| &**p)
 	}
 
+	/// Falls back to magic-byte/encoding sniffing of `prefix` (the first few KB of a file) when
+	/// the filename extension is missing or untrustworthy, e.g. a renamed or extension-less file.
+	pub fn get_parser_for_content(&self, prefix: &[u8]) -> Option<&dyn Parser> {
+		let extension = classify_content(prefix)?;
+		self.get_parser_for_extension(extension)
+	}
+
 	pub fn all_parsers(&self) -> Vec<ParserInfo> {
 		self.parsers
 			.values()
@@ -66,25 +94,212 @@ impl ParserRegistry {
 			registry.register(text::TextParser);
 			registry.register(markdown::MarkdownParser);
 			registry.register(html::HtmlParser);
+			registry.register(org::OrgParser);
+			registry.register(fb2::Fb2Parser);
+			registry.register(odt::OdtParser);
+			registry.register(code::CodeParser);
+			registry.register(pdf::PdfParser);
 			registry
 		})
 	}
 }
 
 pub fn parse_document(context: &ParserContext) -> Result<Document> {
-	let path = std::path::Path::new(&context.file_path);
-	let extension = path
-		.extension()
-		.and_then(|e| e.to_str())
-		.ok_or_else(|| anyhow::anyhow!("No file extension found for: {}", context.file_path))?;
-	let parser = ParserRegistry::global()
-		.get_parser_for_extension(extension)
-		.ok_or_else(|| anyhow::anyhow!("No parser found for extension: .{}", extension))?;
+	if let Some(doc) = cache::get(context) {
+		return Ok(doc);
+	}
+	let doc = parse_document_uncached(context)?;
+	cache::insert(context, doc.clone());
+	Ok(doc)
+}
+
+/// Clears every cached `Document`, e.g. after the caller knows files changed out from under a
+/// `mtime`/`len` check (a network mount with coarse timestamps) or to bound memory explicitly.
+pub fn clear_cache() {
+	cache::clear();
+}
+
+/// Sets the maximum number of parsed documents the cache holds, evicting least-recently-used
+/// entries immediately if the new capacity is smaller than the current contents.
+pub fn set_cache_max_capacity(max_capacity: usize) {
+	cache::set_max_capacity(max_capacity);
+}
+
+fn parse_document_uncached(context: &ParserContext) -> Result<Document> {
+	let path = Path::new(&context.file_path);
+	let extension = path.extension().and_then(|e| e.to_str());
+	let is_structured_zip_extension = matches!(extension.map(str::to_lowercase).as_deref(), Some("epub" | "odt"));
+	if !is_structured_zip_extension && context.bytes.is_none() {
+		if let Some(doc) = try_unwrap_zip_document(context)? {
+			return Ok(doc);
+		}
+	}
+	let by_extension = extension.and_then(|ext| ParserRegistry::global().get_parser_for_extension(ext));
+	// A definitive magic-byte match (a zip or FictionBook signature) overrides even an explicit
+	// extension, so a renamed EPUB/ODT or FB2 misfiled as `.txt` still parses correctly instead of
+	// silently running through whatever parser the wrong extension happens to register.
+	let definitive = if is_structured_zip_extension { None } else { sniff_definitive_extension(&context.file_path) };
+	let registry = ParserRegistry::global();
+	let parser = definitive
+		.and_then(|ext| registry.get_parser_for_extension(ext))
+		.or(by_extension)
+		.or_else(|| sniff_parser_for_file(&context.file_path))
+		.ok_or_else(|| anyhow::anyhow!("No parser found for: {}", context.file_path))?;
 	let mut doc = parser.parse(context)?;
 	doc.compute_stats();
 	Ok(doc)
 }
 
+/// Transparently unwraps zip-wrapped books such as `.fb2.zip` or a bare `story.txt.zip`: when the
+/// file's bytes start with the zip local-file-header magic but it isn't a structured zip format we
+/// already parse natively (EPUB, ODT - by extension, or by a `mimetype` entry inside the archive),
+/// look inside for the first entry whose
+/// extension has a registered parser, extract it to a temporary file, and recursively dispatch to
+/// it - keeping the outer filename as the resulting document's title. Returns `Ok(None)` when the
+/// file isn't a zip, or no entry inside it is recognized, so the caller falls back to the normal
+/// extension/content-sniffing path.
+fn try_unwrap_zip_document(context: &ParserContext) -> Result<Option<Document>> {
+	if !file_starts_with_zip_magic(&context.file_path)? {
+		return Ok(None);
+	}
+	let file = fs::File::open(&context.file_path).with_context(|| format!("Failed to open '{}'", context.file_path))?;
+	let Ok(mut archive) = ZipArchive::new(BufReader::new(file)) else { return Ok(None) };
+	if archive.by_name("mimetype").is_ok() {
+		return Ok(None);
+	}
+	let registry = ParserRegistry::global();
+	let mut inner_entry = None;
+	for i in 0..archive.len() {
+		let Ok(entry) = archive.by_index(i) else { continue };
+		if entry.is_dir() {
+			continue;
+		}
+		let extension = Path::new(entry.name()).extension().and_then(|e| e.to_str()).map(str::to_string);
+		drop(entry);
+		if let Some(extension) = extension {
+			if registry.get_parser_for_extension(&extension).is_some() {
+				inner_entry = Some((i, extension));
+				break;
+			}
+		}
+	}
+	let Some((index, extension)) = inner_entry else { return Ok(None) };
+	let mut entry = archive.by_index(index).context("Failed to read zip entry")?;
+	let mut bytes = Vec::new();
+	entry.read_to_end(&mut bytes).context("Failed to read zip entry contents")?;
+	drop(entry);
+	drop(archive);
+	let temp_path = write_temp_zip_entry(&bytes, &extension)?;
+	let mut inner_context = ParserContext::new(temp_path.to_string_lossy().to_string());
+	if let Some(password) = &context.password {
+		inner_context = inner_context.with_password(password.clone());
+	}
+	let result = parse_document(&inner_context);
+	let _ = fs::remove_file(&temp_path);
+	let mut doc = result?;
+	doc.title = Path::new(&context.file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled").to_string();
+	Ok(Some(doc))
+}
+
+fn file_starts_with_zip_magic(file_path: &str) -> Result<bool> {
+	let mut file = fs::File::open(file_path).with_context(|| format!("Failed to open '{file_path}'"))?;
+	let mut magic = [0u8; 4];
+	let bytes_read = file.read(&mut magic).with_context(|| format!("Failed to read '{file_path}'"))?;
+	Ok(bytes_read == magic.len() && magic == *b"PK\x03\x04")
+}
+
+fn write_temp_zip_entry(bytes: &[u8], extension: &str) -> Result<PathBuf> {
+	static COUNTER: AtomicU64 = AtomicU64::new(0);
+	let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+	let file_name = format!("paperback-zip-entry-{}-{unique}.{extension}", std::process::id());
+	let path = std::env::temp_dir().join(file_name);
+	fs::write(&path, bytes).with_context(|| format!("Failed to write temporary file '{}'", path.display()))?;
+	Ok(path)
+}
+
+const CONTENT_SNIFF_PREFIX_LEN: usize = 8192;
+
+fn sniff_parser_for_file(file_path: &str) -> Option<&'static dyn Parser> {
+	let bytes = std::fs::read(file_path).ok()?;
+	let prefix_len = bytes.len().min(CONTENT_SNIFF_PREFIX_LEN);
+	ParserRegistry::global().get_parser_for_content(&bytes[..prefix_len])
+}
+
+/// Reads `file_path`'s prefix and checks it against `definitive_content_extension`, so a caller
+/// that already has a (possibly wrong) extension in hand can still detect an unambiguous magic
+/// byte mismatch instead of trusting the extension blindly.
+fn sniff_definitive_extension(file_path: &str) -> Option<&'static str> {
+	let bytes = std::fs::read(file_path).ok()?;
+	let prefix_len = bytes.len().min(CONTENT_SNIFF_PREFIX_LEN);
+	definitive_content_extension(&bytes[..prefix_len])
+}
+
+/// Magic-byte signatures unambiguous enough to override even an explicit file extension: a zip
+/// local-file-header (EPUB/ODT, told apart by the ODT `mimetype` string near the start of the
+/// archive) or a FictionBook XML document. Unlike the softer heuristics below
+/// (Markdown/HTML/plain-text sniffing), these can't coincidentally match a file of another type.
+fn definitive_content_extension(prefix: &[u8]) -> Option<&'static str> {
+	const ODT_MIMETYPE: &[u8] = b"application/vnd.oasis.opendocument.text";
+	if prefix.starts_with(b"PK\x03\x04") {
+		let is_odt = prefix.windows(ODT_MIMETYPE.len()).any(|window| window == ODT_MIMETYPE);
+		return Some(if is_odt { "odt" } else { "epub" });
+	}
+	let text_prefix = decode_sniff_prefix(prefix);
+	let trimmed = text_prefix.trim_start();
+	let lower = trimmed.to_ascii_lowercase();
+	if (lower.starts_with("<?xml") && lower.contains("fictionbook")) || lower.starts_with("<fictionbook") {
+		return Some("fb2");
+	}
+	None
+}
+
+/// Classifies a content prefix by magic bytes/text heuristics, returning the extension whose
+/// parser should handle it. Mirrors the `content_inspector` technique of scanning for NUL bytes
+/// and a UTF BOM to tell binary data from text before falling back to `TextParser`.
+fn classify_content(prefix: &[u8]) -> Option<&'static str> {
+	if let Some(extension) = definitive_content_extension(prefix) {
+		return Some(extension);
+	}
+	let text_prefix = decode_sniff_prefix(prefix);
+	let trimmed = text_prefix.trim_start();
+	let lower = trimmed.to_ascii_lowercase();
+	if lower.starts_with("<!doctype html") || lower.starts_with("<html") {
+		return Some("html");
+	}
+	if looks_like_markdown(trimmed) {
+		return Some("md");
+	}
+	if is_probably_text(prefix) {
+		return Some("txt");
+	}
+	None
+}
+
+/// Strips a leading UTF-8/UTF-16 BOM, if present, and lossily decodes the remainder as UTF-8 so
+/// the heuristics below can work on plain `str` prefixes.
+fn decode_sniff_prefix(prefix: &[u8]) -> String {
+	let bytes = if let Some(rest) = prefix.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+		rest
+	} else if prefix.starts_with(&[0xFF, 0xFE]) || prefix.starts_with(&[0xFE, 0xFF]) {
+		&prefix[2..]
+	} else {
+		prefix
+	};
+	String::from_utf8_lossy(bytes).to_string()
+}
+
+fn looks_like_markdown(trimmed: &str) -> bool {
+	let Some(first_line) = trimmed.lines().next() else { return false };
+	let first_line = first_line.trim_start();
+	first_line.starts_with("# ") || first_line.starts_with("## ") || first_line.starts_with("```")
+}
+
+/// A prefix containing a NUL byte is almost certainly binary data, not text, matching the
+/// `content_inspector` heuristic used for this kind of best-effort sniffing.
+fn is_probably_text(prefix: &[u8]) -> bool {
+	!prefix.contains(&0)
+}
+
 pub fn get_all_parsers() -> Vec<ParserInfo> {
 	ParserRegistry::global().all_parsers()
 }
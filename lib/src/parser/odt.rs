@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fs::File, io::BufReader, path::Path};
 
 use anyhow::{Context, Result};
-use roxmltree::{Document as XmlDocument, Node, NodeType};
+use quick_xml::{Reader, events::Event};
 use zip::ZipArchive;
 
 use crate::{
@@ -32,10 +32,10 @@ impl Parser for OdtParser {
 			.with_context(|| format!("Failed to read ODT as zip '{}'", context.file_path))?;
 		let content = read_zip_entry_by_name(&mut archive, "content.xml")
 			.context("ODT file does not contain content.xml or it is empty")?;
-		let xml_doc = XmlDocument::parse(&content).context("Invalid ODT content.xml")?;
 		let mut buffer = DocumentBuffer::new();
 		let mut id_positions = HashMap::new();
-		traverse(xml_doc.root(), &mut buffer, &mut id_positions);
+		stream_parse_content(&content, &mut buffer, &mut id_positions)
+			.with_context(|| format!("Invalid ODT content.xml in '{}'", context.file_path))?;
 		let title =
 			Path::new(&context.file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled").to_string();
 		let toc_items = build_toc_from_buffer(&buffer);
@@ -47,79 +47,118 @@ impl Parser for OdtParser {
 	}
 }
 
-fn traverse(node: Node, buffer: &mut DocumentBuffer, id_positions: &mut HashMap<String, usize>) {
-	if node.node_type() == NodeType::Element {
-		let tag_name = node.tag_name().name();
-		if tag_name == "h" {
-			let level = node.attribute("outline-level").and_then(|s| s.parse::<i32>().ok()).unwrap_or(1);
-			let heading_offset = buffer.current_position();
-			let heading_text = get_element_text(node);
-			if !heading_text.is_empty() {
-				buffer.append(&heading_text);
-				buffer.append("\n");
-				let marker_type = match level {
-					1 => MarkerType::Heading1,
-					2 => MarkerType::Heading2,
-					3 => MarkerType::Heading3,
-					4 => MarkerType::Heading4,
-					5 => MarkerType::Heading5,
-					_ => MarkerType::Heading6,
-				};
-				buffer.add_marker(Marker::new(marker_type, heading_offset).with_text(heading_text).with_level(level));
+/// Converts ODT `content.xml` to plain text in a single `quick-xml` pull-parser pass instead of
+/// building a full `roxmltree` DOM, bounding memory on large documents. Headings (`h`), paragraphs
+/// (`p`) and links (`a`) are recognised by their ODT `text:`/`xlink:` local names and emitted
+/// straight into the `DocumentBuffer` as they're encountered.
+fn stream_parse_content(xml: &str, buffer: &mut DocumentBuffer, id_positions: &mut HashMap<String, usize>) -> Result<()> {
+	let mut reader = Reader::from_str(xml);
+	reader.config_mut().trim_text(false);
+	let mut buf = Vec::new();
+
+	let mut heading_depth: usize = 0;
+	let mut heading_offset = 0usize;
+	let mut heading_text = String::new();
+	let mut heading_level = 1i32;
+
+	let mut link_depth: usize = 0;
+	let mut link_offset = 0usize;
+	let mut link_text = String::new();
+	let mut link_href = String::new();
+
+	loop {
+		match reader.read_event_into(&mut buf).context("Malformed ODT content.xml")? {
+			Event::Eof => break,
+			Event::Start(e) | Event::Empty(e) => {
+				let name = tag_local_name(&e);
+				if name == "h" {
+					if heading_depth == 0 {
+						heading_offset = buffer.current_position();
+						heading_text.clear();
+						heading_level =
+							find_attr_local(&e, "outline-level").and_then(|s| s.parse::<i32>().ok()).unwrap_or(1);
+					}
+					heading_depth += 1;
+				} else if name == "a" {
+					if link_depth == 0 {
+						link_offset = buffer.current_position();
+						link_text.clear();
+						link_href = find_attr_local(&e, "href").unwrap_or_default();
+					}
+					link_depth += 1;
+				} else if heading_depth == 0 && link_depth == 0 {
+					if let Some(id) = find_attr_local(&e, "id") {
+						id_positions.insert(id, buffer.current_position());
+					}
+				}
 			}
-			return; // Don't traverse children, we already got the text
-		}
-		if tag_name == "p" {
-			traverse_children(node, buffer, id_positions);
-			buffer.append("\n");
-			return;
-		}
-		if tag_name == "a" {
-			if let Some(href) = node.attribute("href") {
-				let link_offset = buffer.current_position();
-				let link_text = get_element_text(node);
-				if !link_text.is_empty() {
-					buffer.append(&link_text);
-					buffer.add_marker(
-						Marker::new(MarkerType::Link, link_offset)
-							.with_text(link_text)
-							.with_reference(href.to_string()),
-					);
+			Event::End(e) => {
+				let name = tag_local_name(&e);
+				if name == "h" {
+					heading_depth = heading_depth.saturating_sub(1);
+					if heading_depth == 0 {
+						let trimmed = heading_text.trim();
+						if !trimmed.is_empty() {
+							buffer.append(trimmed);
+							buffer.append("\n");
+							let marker_type = match heading_level {
+								1 => MarkerType::Heading1,
+								2 => MarkerType::Heading2,
+								3 => MarkerType::Heading3,
+								4 => MarkerType::Heading4,
+								5 => MarkerType::Heading5,
+								_ => MarkerType::Heading6,
+							};
+							buffer.add_marker(
+								Marker::new(marker_type, heading_offset)
+									.with_text(trimmed.to_string())
+									.with_level(heading_level),
+							);
+						}
+					}
+				} else if name == "a" {
+					link_depth = link_depth.saturating_sub(1);
+					if link_depth == 0 {
+						let trimmed = link_text.trim();
+						if !link_href.is_empty() && !trimmed.is_empty() {
+							buffer.append(trimmed);
+							buffer.add_marker(
+								Marker::new(MarkerType::Link, link_offset)
+									.with_text(trimmed.to_string())
+									.with_reference(link_href.clone()),
+							);
+						}
+					}
+				} else if name == "p" && heading_depth == 0 && link_depth == 0 {
+					buffer.append("\n");
 				}
 			}
-			return; // Don't traverse children, we already got the text
-		}
-		if let Some(id) = node.attribute("id") {
-			id_positions.insert(id.to_string(), buffer.current_position());
-		}
-	} else if node.node_type() == NodeType::Text {
-		if let Some(text) = node.text() {
-			buffer.append(text);
+			Event::Text(e) => {
+				let content = e.unescape().unwrap_or_default().to_string();
+				if heading_depth > 0 {
+					heading_text.push_str(&content);
+				} else if link_depth > 0 {
+					link_text.push_str(&content);
+				} else {
+					buffer.append(&content);
+				}
+			}
+			_ => {}
 		}
-		return;
-	}
-	traverse_children(node, buffer, id_positions);
-}
-
-fn traverse_children(node: Node, buffer: &mut DocumentBuffer, id_positions: &mut HashMap<String, usize>) {
-	for child in node.children() {
-		traverse(child, buffer, id_positions);
+		buf.clear();
 	}
+	Ok(())
 }
 
-fn get_element_text(node: Node) -> String {
-	let mut text = String::new();
-	collect_text(node, &mut text);
-	text.trim().to_string()
+fn tag_local_name(e: &quick_xml::events::BytesStart) -> String {
+	String::from_utf8_lossy(e.local_name().as_ref()).to_string()
 }
 
-fn collect_text(node: Node, text: &mut String) {
-	if node.node_type() == NodeType::Text {
-		if let Some(t) = node.text() {
-			text.push_str(t);
-		}
-	}
-	for child in node.children() {
-		collect_text(child, text);
-	}
+/// Looks up an attribute by local name, ignoring its namespace prefix (mirroring `roxmltree`'s
+/// `Node::attribute`, which resolves `text:outline-level`/`xlink:href`/`xml:id` the same way).
+fn find_attr_local(e: &quick_xml::events::BytesStart, local_name: &str) -> Option<String> {
+	e.attributes()
+		.filter_map(std::result::Result::ok)
+		.find(|a| a.key.local_name().as_ref() == local_name.as_bytes())
+		.map(|a| String::from_utf8_lossy(&a.value).to_string())
 }
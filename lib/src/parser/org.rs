@@ -0,0 +1,274 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{
+	document::{Document, DocumentBuffer, Marker, MarkerType, ParserContext, ParserFlags, TocItem},
+	parser::Parser,
+	utils::{encoding::convert_to_utf8, text::display_len},
+};
+
+pub struct OrgParser;
+
+struct HeadlineInfo {
+	text: String,
+	level: usize,
+	position: usize,
+	custom_id: Option<String>,
+}
+
+struct FootnoteDef {
+	label: String,
+	position: usize,
+}
+
+struct OrgContent {
+	text: String,
+	title: Option<String>,
+	headlines: Vec<HeadlineInfo>,
+	links: Vec<(usize, (String, String))>,
+	footnote_refs: Vec<(usize, String)>,
+	footnote_defs: Vec<FootnoteDef>,
+}
+
+impl Parser for OrgParser {
+	fn name(&self) -> &str {
+		"Org Files"
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["org"]
+	}
+
+	fn supported_flags(&self) -> ParserFlags {
+		ParserFlags::SUPPORTS_TOC | ParserFlags::SUPPORTS_LISTS
+	}
+
+	fn parse(&self, context: &ParserContext) -> Result<Document, String> {
+		let bytes =
+			fs::read(&context.file_path).map_err(|e| format!("Failed to open Org file '{}': {}", context.file_path, e))?;
+		if bytes.is_empty() {
+			return Err(format!("Org file is empty: {}", context.file_path));
+		}
+		let org_content = convert_to_utf8(&bytes);
+		let content = parse_org_to_text(&org_content)?;
+		let title = content.title.unwrap_or_else(|| {
+			Path::new(&context.file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled").to_string()
+		});
+		let mut buffer = DocumentBuffer::with_content(content.text);
+		for headline in &content.headlines {
+			let marker_type = match headline.level {
+				1 => MarkerType::Heading1,
+				2 => MarkerType::Heading2,
+				3 => MarkerType::Heading3,
+				4 => MarkerType::Heading4,
+				5 => MarkerType::Heading5,
+				_ => MarkerType::Heading6,
+			};
+			buffer.add_marker(
+				Marker::new(marker_type, headline.position)
+					.with_text(headline.text.clone())
+					.with_level(headline.level as i32),
+			);
+		}
+		for (position, (text, target)) in content.links {
+			buffer.add_marker(Marker::new(MarkerType::Link, position).with_text(text).with_reference(target));
+		}
+		for (position, label) in &content.footnote_refs {
+			buffer.add_marker(
+				Marker::new(MarkerType::Link, *position)
+					.with_text(format!("[{label}]"))
+					.with_reference(format!("fn:{label}")),
+			);
+		}
+		let mut id_positions = HashMap::new();
+		for headline in &content.headlines {
+			if let Some(custom_id) = &headline.custom_id {
+				id_positions.insert(custom_id.clone(), headline.position);
+			}
+		}
+		for footnote_def in &content.footnote_defs {
+			id_positions.insert(format!("fn:{}", footnote_def.label), footnote_def.position);
+		}
+		let toc_items = build_toc_from_headlines(&content.headlines);
+		let mut doc = Document::new().with_title(title);
+		doc.set_buffer(buffer);
+		doc.toc_items = toc_items;
+		doc.id_positions = id_positions;
+		doc.compute_stats();
+		Ok(doc)
+	}
+}
+
+fn parse_org_to_text(org: &str) -> Result<OrgContent, String> {
+	let mut text = String::new();
+	let mut title = None;
+	let mut headlines: Vec<HeadlineInfo> = Vec::new();
+	let mut links = Vec::new();
+	let mut footnote_refs = Vec::new();
+	let mut footnote_defs = Vec::new();
+	let mut in_block: Option<String> = None;
+	let mut in_properties = false;
+	for line in org.lines() {
+		let trimmed = line.trim();
+		if let Some(name) = block_delimiter_name(trimmed, "#+BEGIN_") {
+			in_block = Some(name);
+			continue;
+		}
+		if block_delimiter_name(trimmed, "#+END_").is_some() {
+			in_block = None;
+			continue;
+		}
+		if let Some(block_name) = &in_block {
+			if block_name != "COMMENT" {
+				text.push_str(line);
+				text.push('\n');
+			}
+			continue;
+		}
+		if title.is_none() {
+			if let Some(rest) = strip_prefix_ignore_case(trimmed, "#+TITLE:") {
+				title = Some(rest.trim().to_string());
+				continue;
+			}
+		}
+		if trimmed == ":PROPERTIES:" {
+			in_properties = true;
+			continue;
+		}
+		if trimmed == ":END:" && in_properties {
+			in_properties = false;
+			continue;
+		}
+		if in_properties {
+			if let Some(rest) = trimmed.strip_prefix(":CUSTOM_ID:") {
+				if let Some(headline) = headlines.last_mut() {
+					headline.custom_id = Some(rest.trim().to_string());
+				}
+			}
+			continue;
+		}
+		if let Some((level, headline_text)) = parse_headline(line) {
+			let position = display_len(&text);
+			headlines.push(HeadlineInfo { text: headline_text.clone(), level, position, custom_id: None });
+			text.push_str(&headline_text);
+			text.push_str("\n\n");
+			continue;
+		}
+		if let Some((label, contents)) = parse_footnote_def(trimmed) {
+			let position = display_len(&text);
+			footnote_defs.push(FootnoteDef { label, position });
+			text.push_str(&contents);
+			text.push_str("\n\n");
+			continue;
+		}
+		append_inline(line, &mut text, &mut links, &mut footnote_refs);
+		text.push('\n');
+	}
+	Ok(OrgContent { text, title, headlines, links, footnote_refs, footnote_defs })
+}
+
+/// Matches a `#+BEGIN_<NAME>`/`#+END_<NAME>` delimiter case-insensitively and returns the
+/// upper-cased block name (ignoring any trailing language tag, e.g. `#+BEGIN_SRC rust`).
+fn block_delimiter_name(trimmed: &str, prefix: &str) -> Option<String> {
+	let rest = strip_prefix_ignore_case(trimmed, prefix)?;
+	rest.split_whitespace().next().map(str::to_uppercase)
+}
+
+fn strip_prefix_ignore_case<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+	if line.len() < prefix.len() {
+		return None;
+	}
+	if line[..prefix.len()].eq_ignore_ascii_case(prefix) { Some(&line[prefix.len()..]) } else { None }
+}
+
+/// Headline level is the count of leading `*` characters up to the first space.
+fn parse_headline(line: &str) -> Option<(usize, String)> {
+	let stars_end = line.find(|c: char| c != '*')?;
+	if stars_end == 0 || !line[stars_end..].starts_with(' ') {
+		return None;
+	}
+	Some((stars_end, line[stars_end..].trim().to_string()))
+}
+
+fn parse_footnote_def(trimmed: &str) -> Option<(String, String)> {
+	let rest = trimmed.strip_prefix("[fn:")?;
+	let close = rest.find(']')?;
+	let label = &rest[..close];
+	if !is_valid_footnote_label(label) {
+		return None;
+	}
+	Some((label.to_string(), rest[close + 1..].trim().to_string()))
+}
+
+fn is_valid_footnote_label(label: &str) -> bool {
+	!label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Scans a body line for `[[target][description]]` / `[[target]]` links and `[fn:LABEL]`
+/// footnote references, appending plain text (and recording marker positions) as it goes.
+fn append_inline(
+	line: &str,
+	text: &mut String,
+	links: &mut Vec<(usize, (String, String))>,
+	footnote_refs: &mut Vec<(usize, String)>,
+) {
+	let mut rest = line;
+	loop {
+		if let Some(after_open) = rest.strip_prefix("[[") {
+			if let Some(close) = after_open.find("]]") {
+				let inner = &after_open[..close];
+				let (target, description) =
+					inner.find("][").map_or((inner, inner), |sep| (&inner[..sep], &inner[sep + 2..]));
+				links.push((display_len(text), (description.to_string(), target.to_string())));
+				text.push_str(description);
+				rest = &after_open[close + 2..];
+				continue;
+			}
+		}
+		if let Some(after_fn) = rest.strip_prefix("[fn:") {
+			if let Some(close) = after_fn.find(']') {
+				let label = &after_fn[..close];
+				if is_valid_footnote_label(label) {
+					footnote_refs.push((display_len(text), label.to_string()));
+					rest = &after_fn[close + 1..];
+					continue;
+				}
+			}
+		}
+		let Some(ch) = rest.chars().next() else { break };
+		text.push(ch);
+		rest = &rest[ch.len_utf8()..];
+	}
+}
+
+fn build_toc_from_headlines(headlines: &[HeadlineInfo]) -> Vec<TocItem> {
+	if headlines.is_empty() {
+		return Vec::new();
+	}
+	let mut toc = Vec::new();
+	let mut stack: Vec<(usize, Vec<usize>)> = Vec::new();
+	for headline in headlines {
+		let item = TocItem::new(headline.text.clone(), String::new(), headline.position);
+		while let Some((level, _)) = stack.last() {
+			if *level < headline.level {
+				break;
+			}
+			stack.pop();
+		}
+		if stack.is_empty() {
+			toc.push(item);
+			stack.push((headline.level, vec![toc.len() - 1]));
+		} else {
+			let (_, path) = stack.last().unwrap();
+			let mut current = &mut toc;
+			for &idx in &path[..path.len() - 1] {
+				current = &mut current[idx].children;
+			}
+			let parent_idx = *path.last().unwrap();
+			current[parent_idx].children.push(item);
+			let mut new_path = path.clone();
+			new_path.push(current[parent_idx].children.len() - 1);
+			stack.push((headline.level, new_path));
+		}
+	}
+	toc
+}
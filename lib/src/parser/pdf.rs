@@ -1,4 +1,5 @@
 use std::{
+	collections::HashMap,
 	ffi::{CStr, CString, c_void},
 	ptr,
 };
@@ -6,7 +7,7 @@ use std::{
 use anyhow::{Result, anyhow, bail};
 
 use crate::{
-	document::{Document, DocumentBuffer, Marker, MarkerType, ParserContext, ParserFlags, TocItem},
+	document::{Document, DocumentBuffer, DocumentPermissions, Marker, MarkerType, ParserContext, ParserFlags, TocItem},
 	parser::{PASSWORD_REQUIRED_ERROR_PREFIX, Parser, utils::extract_title_from_path},
 	utils::text::{collapse_whitespace, trim_string},
 };
@@ -23,14 +24,22 @@ impl Parser for PdfParser {
 	}
 
 	fn supported_flags(&self) -> ParserFlags {
-		ParserFlags::SUPPORTS_PAGES | ParserFlags::SUPPORTS_TOC
+		let mut flags = ParserFlags::SUPPORTS_PAGES | ParserFlags::SUPPORTS_TOC;
+		if cfg!(feature = "ocr") {
+			flags |= ParserFlags::SUPPORTS_OCR;
+		}
+		flags
 	}
 
 	fn parse(&self, context: &ParserContext) -> Result<Document> {
 		let _library = PdfiumLibrary::new();
-		let document = PdfDocument::load(&context.file_path, context.password.as_deref())?;
+		let document = match &context.bytes {
+			Some(bytes) => PdfDocument::load_from_bytes(bytes.clone(), context.password.as_deref())?,
+			None => PdfDocument::load(&context.file_path, context.password.as_deref())?,
+		};
 		let mut buffer = DocumentBuffer::new();
 		let mut page_offsets = Vec::new();
+		let mut pending_links = Vec::new();
 		let page_count = document.page_count()?;
 		for page_index in 0..page_count {
 			let marker_position = buffer.current_position();
@@ -43,23 +52,50 @@ impl Parser for PdfParser {
 				None => continue,
 			};
 			if let Some(text_page) = page.load_text_page() {
-				let raw_text = text_page.extract_text();
-				let lines = process_text_lines(&raw_text);
+				let chars = text_page.extract_chars();
+				let (lines, char_offsets) = if text_page.char_count() <= OCR_EMPTY_PAGE_CHAR_THRESHOLD {
+					let ocr_lines = if context.enable_ocr { ocr_page_text_with_default_backend(&page) } else { Vec::new() };
+					(ocr_lines, HashMap::new())
+				} else {
+					reconstruct_layout(&chars)
+				};
+				for marker in web_link_markers(&text_page, &char_offsets, marker_position) {
+					buffer.add_marker(marker);
+				}
+				pending_links.extend(collect_internal_link_markers(
+					document.handle,
+					&page,
+					&chars,
+					&char_offsets,
+					marker_position,
+				));
 				for line in lines {
 					buffer.append(&line);
 					buffer.append("\n");
 				}
 			}
 		}
+		for pending in pending_links {
+			if let Some(offset) = page_offsets.get(pending.dest_page_index as usize).copied() {
+				buffer.add_marker(
+					Marker::new(MarkerType::Link, pending.position)
+						.with_text(pending.anchor_text)
+						.with_reference(format!("#{offset}")),
+				);
+			}
+		}
 		let title =
 			document.extract_metadata(b"Title\0").unwrap_or_else(|| extract_title_from_path(&context.file_path));
 		let author = document.extract_metadata(b"Author\0").unwrap_or_default();
 		let toc_items = document.extract_toc(&page_offsets);
+		let (encrypted, permissions) = document.read_permissions();
 		let mut doc = Document::new();
 		doc.set_buffer(buffer);
 		doc.title = title;
 		doc.author = author;
 		doc.toc_items = toc_items;
+		doc.encrypted = encrypted;
+		doc.permissions = permissions;
 		Ok(doc)
 	}
 }
@@ -85,24 +121,39 @@ impl Drop for PdfiumLibrary {
 
 struct PdfDocument {
 	handle: ffi::FPDF_DOCUMENT,
+	/// Keeps the in-memory buffer alive for the document's lifetime: PDFium reads directly from it
+	/// on demand rather than copying it in `FPDF_LoadMemDocument`. Unused when loaded from a path.
+	_backing_bytes: Option<Vec<u8>>,
 }
 
 impl PdfDocument {
 	fn load(path: &str, password: Option<&str>) -> Result<Self> {
 		let path_cstr = CString::new(path).map_err(|_| anyhow!("PDF path contains embedded NUL bytes"))?;
-		let password_cstr = match password {
-			Some(pwd) if !pwd.is_empty() => {
-				Some(CString::new(pwd).map_err(|_| anyhow!("PDF password contains embedded NUL bytes"))?)
-			}
-			_ => None,
-		};
+		let password_cstr = build_password_cstring(password)?;
 		let handle = unsafe {
 			ffi::FPDF_LoadDocument(path_cstr.as_ptr(), password_cstr.as_ref().map_or(ptr::null(), |pwd| pwd.as_ptr()))
 		};
 		if handle.is_null() {
 			return Err(map_pdfium_error("Failed to open PDF document"));
 		}
-		Ok(Self { handle })
+		Ok(Self { handle, _backing_bytes: None })
+	}
+
+	/// Loads a PDF directly from an in-memory buffer via `FPDF_LoadMemDocument`, so callers with a
+	/// ZIP entry or a network download don't have to spill it to a temp file first.
+	fn load_from_bytes(data: Vec<u8>, password: Option<&str>) -> Result<Self> {
+		let password_cstr = build_password_cstring(password)?;
+		let handle = unsafe {
+			ffi::FPDF_LoadMemDocument(
+				data.as_ptr().cast::<c_void>(),
+				data.len() as i32,
+				password_cstr.as_ref().map_or(ptr::null(), |pwd| pwd.as_ptr()),
+			)
+		};
+		if handle.is_null() {
+			return Err(map_pdfium_error("Failed to open PDF document from memory"));
+		}
+		Ok(Self { handle, _backing_bytes: Some(data) })
 	}
 
 	fn page_count(&self) -> Result<i32> {
@@ -141,6 +192,18 @@ impl PdfDocument {
 		}
 		extract_outline_items(self.handle, first, page_offsets)
 	}
+
+	/// Reads encryption state via `FPDF_GetSecurityHandlerRevision` (negative when the document
+	/// carries no security handler at all) and, when encrypted, decodes the owner-granted
+	/// permission bits from `FPDF_GetDocPermissions`.
+	fn read_permissions(&self) -> (bool, Option<DocumentPermissions>) {
+		let revision = unsafe { ffi::FPDF_GetSecurityHandlerRevision(self.handle) };
+		if revision < 0 {
+			return (false, None);
+		}
+		let bits = unsafe { ffi::FPDF_GetDocPermissions(self.handle) };
+		(true, Some(DocumentPermissions::from_bits(bits)))
+	}
 }
 
 impl Drop for PdfDocument {
@@ -179,19 +242,30 @@ struct PdfTextPage {
 }
 
 impl PdfTextPage {
-	fn extract_text(&self) -> String {
-		let char_count = unsafe { ffi::FPDFText_CountChars(self.handle) };
+	/// The number of glyphs PDFium reports for this page's native text layer, independent of how
+	/// many of them `extract_chars` is able to box/keep - used to decide whether a page is
+	/// image-only (scanned) without a layout glyph dropped for a missing box skewing the count.
+	fn char_count(&self) -> i32 {
+		unsafe { ffi::FPDFText_CountChars(self.handle) }
+	}
+
+	fn extract_chars(&self) -> Vec<CharInfo> {
+		let char_count = self.char_count();
 		if char_count <= 0 {
-			return String::new();
+			return Vec::new();
 		}
-		let mut buffer = vec![0u16; (char_count + 1) as usize];
-		let written = unsafe { ffi::FPDFText_GetText(self.handle, 0, char_count, buffer.as_mut_ptr()) };
-		if written <= 1 {
-			return String::new();
+		let mut chars = Vec::with_capacity(char_count as usize);
+		for index in 0..char_count {
+			let unicode = unsafe { ffi::FPDFText_GetUnicode(self.handle, index) };
+			let (mut left, mut right, mut bottom, mut top) = (0.0, 0.0, 0.0, 0.0);
+			let has_box =
+				unsafe { ffi::FPDFText_GetCharBox(self.handle, index, &mut left, &mut right, &mut bottom, &mut top) };
+			if has_box == 0 || unicode == 0 {
+				continue;
+			}
+			chars.push(CharInfo { unicode, left, right, bottom, top, raw_index: index });
 		}
-		let actual_len = (written as usize).saturating_sub(1);
-		buffer.truncate(actual_len);
-		String::from_utf16_lossy(&buffer)
+		chars
 	}
 }
 
@@ -205,14 +279,498 @@ impl Drop for PdfTextPage {
 	}
 }
 
-fn process_text_lines(raw_text: &str) -> Vec<String> {
-	raw_text
+const POINTS_PER_INCH: f64 = 72.0;
+const OCR_RENDER_DPI: f64 = 200.0;
+/// A page is treated as image-only (scanned) - and thus worth the cost of rendering and running
+/// OCR - only once its native text layer has at most this many characters, so born-digital pages
+/// with a normal text layer never pay the rendering/OCR cost.
+const OCR_EMPTY_PAGE_CHAR_THRESHOLD: i32 = 0;
+
+/// An RGBA bitmap rendered from a PDF page at a given DPI, ready to hand to an OCR backend.
+struct PdfBitmap {
+	/// Row-major RGBA pixels, `width * height * 4` bytes, no row padding.
+	pixels: Vec<u8>,
+	width: i32,
+	height: i32,
+}
+
+impl PdfBitmap {
+	/// Renders `page` to an RGBA bitmap at `dpi`, converting PDFium's native BGRA buffer to RGBA
+	/// and stripping any stride padding so the result is a tightly packed pixel buffer.
+	fn render(page: &PdfPage, dpi: f64) -> Option<Self> {
+		let page_width_pt = f64::from(unsafe { ffi::FPDF_GetPageWidthF(page.handle) });
+		let page_height_pt = f64::from(unsafe { ffi::FPDF_GetPageHeightF(page.handle) });
+		if page_width_pt <= 0.0 || page_height_pt <= 0.0 {
+			return None;
+		}
+		let scale = dpi / POINTS_PER_INCH;
+		let width = ((page_width_pt * scale).round() as i32).max(1);
+		let height = ((page_height_pt * scale).round() as i32).max(1);
+		let bitmap = unsafe { ffi::FPDFBitmap_Create(width, height, 1) };
+		if bitmap.is_null() {
+			return None;
+		}
+		unsafe {
+			ffi::FPDF_RenderPageBitmap(bitmap, page.handle, 0, 0, width, height, 0, 0);
+		}
+		let stride = unsafe { ffi::FPDFBitmap_GetStride(bitmap) };
+		let buffer = unsafe { ffi::FPDFBitmap_GetBuffer(bitmap) };
+		let pixels =
+			if buffer.is_null() { None } else { Some(bgra_buffer_to_rgba(buffer.cast(), stride, width, height)) };
+		unsafe {
+			ffi::FPDFBitmap_Destroy(bitmap);
+		}
+		pixels.map(|pixels| Self { pixels, width, height })
+	}
+}
+
+fn bgra_buffer_to_rgba(buffer: *const u8, stride: i32, width: i32, height: i32) -> Vec<u8> {
+	let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+	for row in 0..height {
+		let row_start = unsafe { buffer.add((row * stride) as usize) };
+		for col in 0..width {
+			let pixel = unsafe { row_start.add((col * 4) as usize) };
+			let (b, g, r, a) = unsafe { (*pixel, *pixel.add(1), *pixel.add(2), *pixel.add(3)) };
+			pixels.extend_from_slice(&[r, g, b, a]);
+		}
+	}
+	pixels
+}
+
+/// Runs OCR with the default backend, or produces no lines at all on builds compiled without the
+/// `ocr` feature - that feature gate is what actually lets callers without `libtesseract`
+/// available link this crate; `context.enable_ocr` only controls the runtime fallback once linked.
+#[cfg(feature = "ocr")]
+fn ocr_page_text_with_default_backend(page: &PdfPage) -> Vec<String> {
+	ocr_page_text(page, &TesseractOcr).unwrap_or_default()
+}
+
+#[cfg(not(feature = "ocr"))]
+fn ocr_page_text_with_default_backend(_page: &PdfPage) -> Vec<String> {
+	Vec::new()
+}
+
+/// Renders `page` and runs it through `backend`, returning cleaned, non-empty lines - the
+/// fallback path for scanned pages whose native text layer is empty.
+#[cfg(feature = "ocr")]
+fn ocr_page_text(page: &PdfPage, backend: &dyn OcrBackend) -> Option<Vec<String>> {
+	let bitmap = PdfBitmap::render(page, OCR_RENDER_DPI)?;
+	let recognized = backend.recognize(&bitmap.pixels, bitmap.width, bitmap.height).ok()?;
+	let lines: Vec<String> = recognized
 		.lines()
 		.filter_map(|line| {
 			let collapsed = collapse_whitespace(line);
 			let trimmed = trim_string(&collapsed);
 			if trimmed.is_empty() { None } else { Some(trimmed) }
 		})
+		.collect();
+	if lines.is_empty() { None } else { Some(lines) }
+}
+
+/// A pluggable text-recognition backend for rendered page images, so the rendering/threshold
+/// logic above doesn't need to know which OCR engine is installed.
+#[cfg(feature = "ocr")]
+trait OcrBackend {
+	/// Recognizes text from a `width * height * 4`-byte, row-major RGBA pixel buffer.
+	fn recognize(&self, pixels: &[u8], width: i32, height: i32) -> Result<String>;
+}
+
+/// The default `OcrBackend`, bound directly to `libtesseract`'s C API the same way `pdf::ffi`
+/// binds `libpdfium` - no `tesseract`-wrapping crate in this tree. Only compiled in under the
+/// `ocr` feature, since linking it requires `libtesseract` to be present on the build host.
+#[cfg(feature = "ocr")]
+struct TesseractOcr;
+
+#[cfg(feature = "ocr")]
+impl OcrBackend for TesseractOcr {
+	fn recognize(&self, pixels: &[u8], width: i32, height: i32) -> Result<String> {
+		let handle = unsafe { tesseract_ffi::TessBaseAPICreate() };
+		if handle.is_null() {
+			bail!("Failed to create Tesseract OCR engine");
+		}
+		let language = CString::new("eng").expect("static string contains no NUL bytes");
+		let init_result = unsafe { tesseract_ffi::TessBaseAPIInit3(handle, ptr::null(), language.as_ptr()) };
+		if init_result != 0 {
+			unsafe {
+				tesseract_ffi::TessBaseAPIDelete(handle);
+			}
+			bail!("Failed to initialize Tesseract OCR engine");
+		}
+		let text = unsafe {
+			tesseract_ffi::TessBaseAPISetImage(handle, pixels.as_ptr(), width, height, 4, width * 4);
+			let raw_text = tesseract_ffi::TessBaseAPIGetUTF8Text(handle);
+			let text = if raw_text.is_null() {
+				String::new()
+			} else {
+				let recognized = CStr::from_ptr(raw_text).to_string_lossy().into_owned();
+				tesseract_ffi::TessDeleteText(raw_text);
+				recognized
+			};
+			tesseract_ffi::TessBaseAPIEnd(handle);
+			tesseract_ffi::TessBaseAPIDelete(handle);
+			text
+		};
+		Ok(text)
+	}
+}
+
+#[derive(Clone, Copy)]
+struct CharInfo {
+	unicode: u32,
+	left: f64,
+	right: f64,
+	bottom: f64,
+	top: f64,
+	/// This glyph's index in PDFium's native per-page char stream (`FPDFText_GetUnicode`'s
+	/// `index`), preserved so link markers can be placed by glyph identity - see
+	/// `reconstruct_layout`'s returned offset map.
+	raw_index: i32,
+}
+
+const LAYOUT_COLUMN_BINS: usize = 40;
+
+/// A reconstructed line of text paired with each rendered character's originating glyph
+/// (`raw_index`, `None` for a space synthesized at a wide horizontal gap rather than copied from a
+/// glyph), so a link marker can later be placed by glyph identity instead of a substring search.
+struct RenderedLine {
+	text: String,
+	raw_indices: Vec<Option<i32>>,
+}
+
+/// Reconstructs reading-order lines from raw glyph boxes instead of trusting PDFium's native
+/// character stream order, mirroring how MuPDF/Poppler derive layout from glyph coordinates:
+/// clusters glyphs into lines by vertical center, splits off a second column when the page has a
+/// persistent wide whitespace band, sorts each line left-to-right inserting spaces at wide
+/// horizontal gaps, and joins words split across a hyphenated line break. Alongside the lines,
+/// returns a map from each rendered glyph's `raw_index` to its UTF-16 offset within the lines
+/// joined by `"\n"`, matching how `DocumentBuffer::append` advances position in `PdfParser::parse`.
+fn reconstruct_layout(chars: &[CharInfo]) -> (Vec<String>, HashMap<i32, usize>) {
+	if chars.is_empty() {
+		return (Vec::new(), HashMap::new());
+	}
+	let median_height = median(chars.iter().map(|c| (c.top - c.bottom).abs()).collect());
+	let median_advance = median(chars.iter().map(|c| (c.right - c.left).abs()).collect());
+	let tolerance = (median_height / 2.0).max(0.5);
+	let space_threshold = (median_advance * 0.25).max(0.01);
+
+	let (left_chars, right_chars) = match detect_column_boundary(chars) {
+		Some(boundary) => chars.iter().copied().partition(|c| (c.left + c.right) / 2.0 < boundary),
+		None => (chars.to_vec(), Vec::new()),
+	};
+
+	let mut lines = lines_for_column(&left_chars, tolerance, space_threshold);
+	lines.extend(lines_for_column(&right_chars, tolerance, space_threshold));
+	let lines = join_hyphenated_lines(lines);
+
+	let mut char_offsets = HashMap::new();
+	let mut offset = 0usize;
+	for (index, line) in lines.iter().enumerate() {
+		if index > 0 {
+			offset += 1;
+		}
+		for (ch, raw_index) in line.text.chars().zip(line.raw_indices.iter().copied()) {
+			if let Some(raw_index) = raw_index {
+				char_offsets.entry(raw_index).or_insert(offset);
+			}
+			offset += ch.len_utf16();
+		}
+	}
+	(lines.into_iter().map(|line| line.text).collect(), char_offsets)
+}
+
+fn lines_for_column(chars: &[CharInfo], tolerance: f64, space_threshold: f64) -> Vec<RenderedLine> {
+	if chars.is_empty() {
+		return Vec::new();
+	}
+	let mut sorted = chars.to_vec();
+	sorted.sort_by(|a, b| vertical_center(b).partial_cmp(&vertical_center(a)).unwrap_or(std::cmp::Ordering::Equal));
+	let mut clusters: Vec<Vec<CharInfo>> = Vec::new();
+	for ch in sorted {
+		let center = vertical_center(&ch);
+		let fits_last_cluster = clusters
+			.last()
+			.map(|cluster| cluster.iter().map(vertical_center).sum::<f64>() / cluster.len() as f64)
+			.is_some_and(|last_center| (center - last_center).abs() <= tolerance);
+		if fits_last_cluster {
+			clusters.last_mut().expect("checked above").push(ch);
+		} else {
+			clusters.push(vec![ch]);
+		}
+	}
+	clusters.iter().map(|line| render_line(line, space_threshold)).collect()
+}
+
+fn vertical_center(c: &CharInfo) -> f64 {
+	(c.top + c.bottom) / 2.0
+}
+
+fn render_line(line: &[CharInfo], space_threshold: f64) -> RenderedLine {
+	let mut sorted = line.to_vec();
+	sorted.sort_by(|a, b| a.left.partial_cmp(&b.left).unwrap_or(std::cmp::Ordering::Equal));
+	let mut text = String::new();
+	let mut raw_indices: Vec<Option<i32>> = Vec::new();
+	let mut prev_right: Option<f64> = None;
+	for ch in &sorted {
+		if let Some(prev) = prev_right {
+			if ch.left - prev > space_threshold && !text.ends_with(' ') {
+				text.push(' ');
+				raw_indices.push(None);
+			}
+		}
+		if let Some(glyph) = char::from_u32(ch.unicode) {
+			text.push(glyph);
+			raw_indices.push(Some(ch.raw_index));
+		}
+		prev_right = Some(ch.right);
+	}
+	let (text, raw_indices) = collapse_whitespace_with_positions(&text, &raw_indices);
+	let (text, raw_indices) = trim_with_positions(&text, &raw_indices);
+	RenderedLine { text, raw_indices }
+}
+
+/// Mirrors `utils::text::collapse_whitespace` character-for-character, but carries each surviving
+/// character's `raw_indices` entry along so the two stay in lockstep - calling the plain utility
+/// and a position array through it separately would let them drift out of sync.
+fn collapse_whitespace_with_positions(text: &str, raw_indices: &[Option<i32>]) -> (String, Vec<Option<i32>>) {
+	let mut result = String::with_capacity(text.len());
+	let mut positions = Vec::with_capacity(raw_indices.len());
+	let mut prev_was_space = false;
+	for (ch, raw_index) in text.chars().zip(raw_indices.iter().copied()) {
+		let is_space = ch.is_whitespace() || ch == '\u{00A0}';
+		if is_space {
+			if !prev_was_space {
+				result.push(' ');
+				positions.push(raw_index);
+				prev_was_space = true;
+			}
+		} else {
+			result.push(ch);
+			positions.push(raw_index);
+			prev_was_space = false;
+		}
+	}
+	(result, positions)
+}
+
+/// Mirrors `utils::text::trim_string`, keeping `raw_indices` aligned to the trimmed text.
+fn trim_with_positions(text: &str, raw_indices: &[Option<i32>]) -> (String, Vec<Option<i32>>) {
+	let is_trim_char = |c: char| c.is_whitespace() || c == '\u{00A0}';
+	let chars: Vec<char> = text.chars().collect();
+	let start = chars.iter().position(|c| !is_trim_char(*c)).unwrap_or(chars.len());
+	let end = chars.iter().rposition(|c| !is_trim_char(*c)).map_or(start, |i| i + 1);
+	(chars[start..end].iter().collect(), raw_indices[start..end].to_vec())
+}
+
+/// Finds a single page-wide column boundary by binning glyph x-coverage and looking, away from
+/// the page margins, for the widest run of bins no glyph ever touches - a persistent vertical
+/// whitespace band, the same signal MuPDF/Poppler use to detect multi-column layouts.
+fn detect_column_boundary(chars: &[CharInfo]) -> Option<f64> {
+	let min_x = chars.iter().map(|c| c.left).fold(f64::MAX, f64::min);
+	let max_x = chars.iter().map(|c| c.right).fold(f64::MIN, f64::max);
+	let width = max_x - min_x;
+	if width <= 0.0 {
+		return None;
+	}
+	let bin_width = width / LAYOUT_COLUMN_BINS as f64;
+	let mut covered = vec![false; LAYOUT_COLUMN_BINS];
+	for ch in chars {
+		let start_bin = (((ch.left - min_x) / bin_width) as usize).min(LAYOUT_COLUMN_BINS - 1);
+		let end_bin = (((ch.right - min_x) / bin_width) as usize).min(LAYOUT_COLUMN_BINS - 1);
+		for bin in covered.iter_mut().take(end_bin + 1).skip(start_bin) {
+			*bin = true;
+		}
+	}
+	let margin = LAYOUT_COLUMN_BINS / 5;
+	let mut best_gap: Option<(usize, usize)> = None;
+	let mut gap_start = None;
+	for (bin, is_covered) in covered.iter().enumerate().take(LAYOUT_COLUMN_BINS - margin).skip(margin) {
+		if *is_covered {
+			if let Some(start) = gap_start.take() {
+				record_gap(&mut best_gap, start, bin - 1);
+			}
+		} else if gap_start.is_none() {
+			gap_start = Some(bin);
+		}
+	}
+	if let Some(start) = gap_start {
+		record_gap(&mut best_gap, start, LAYOUT_COLUMN_BINS - margin - 1);
+	}
+	let (start, end) = best_gap?;
+	let min_gap_bins = ((LAYOUT_COLUMN_BINS as f64 * 0.03).ceil() as usize).max(1);
+	if end + 1 - start < min_gap_bins {
+		return None;
+	}
+	let center_bin = (start + end) as f64 / 2.0 + 0.5;
+	Some(min_x + center_bin * bin_width)
+}
+
+fn record_gap(best: &mut Option<(usize, usize)>, start: usize, end: usize) {
+	let width = end.saturating_sub(start);
+	let is_widest_so_far = match *best {
+		None => true,
+		Some((s, e)) => e.saturating_sub(s) < width,
+	};
+	if is_widest_so_far {
+		*best = Some((start, end));
+	}
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+	if values.is_empty() {
+		return 0.0;
+	}
+	values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+	let mid = values.len() / 2;
+	if values.len() % 2 == 0 { (values[mid - 1] + values[mid]) / 2.0 } else { values[mid] }
+}
+
+/// Rejoins a word split across a hyphenated line break (a trailing hyphen followed by a line that
+/// starts lowercase), the same heuristic MuPDF/Poppler use to undo PDF line wrapping.
+fn join_hyphenated_lines(lines: Vec<RenderedLine>) -> Vec<RenderedLine> {
+	let mut result: Vec<RenderedLine> = Vec::with_capacity(lines.len());
+	for line in lines {
+		let joins_previous = result
+			.last()
+			.is_some_and(|last: &RenderedLine| ends_with_hyphen(&last.text) && starts_with_lowercase(&line.text));
+		if joins_previous {
+			let last = result.last_mut().expect("checked above");
+			last.text.pop();
+			last.raw_indices.pop();
+			last.text.push_str(&line.text);
+			last.raw_indices.extend(line.raw_indices);
+		} else {
+			result.push(line);
+		}
+	}
+	result
+}
+
+fn ends_with_hyphen(line: &str) -> bool {
+	matches!(line.chars().last(), Some('-' | '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}'))
+}
+
+fn starts_with_lowercase(line: &str) -> bool {
+	line.chars().next().is_some_and(char::is_lowercase)
+}
+
+/// A GoTo link annotation's anchor, pending buffer-offset resolution: `dest_page_index` can name a
+/// page later in the document than the one this link appears on, so its offset isn't known until
+/// every page has been walked and `page_offsets` is complete.
+struct PendingLinkMarker {
+	position: usize,
+	anchor_text: String,
+	dest_page_index: i32,
+}
+
+/// Builds web-link (`https://...`) markers for a page using PDFium's text-based web-link scanner,
+/// placing each marker at the lowest reconstructed-text offset among the glyphs in its text range -
+/// looked up by glyph identity in `char_offsets` rather than by re-finding the anchor text, since a
+/// PDF without explicit space glyphs never reproduces verbatim in the reflowed, space-inserted text.
+fn web_link_markers(text_page: &PdfTextPage, char_offsets: &HashMap<i32, usize>, page_start: usize) -> Vec<Marker> {
+	let link_page = unsafe { ffi::FPDFLink_LoadWebLinks(text_page.handle) };
+	if link_page.is_null() {
+		return Vec::new();
+	}
+	let count = unsafe { ffi::FPDFLink_CountWebLinks(link_page) };
+	let mut markers = Vec::new();
+	for index in 0..count {
+		let (mut start_char_index, mut char_count) = (0, 0);
+		let has_range = unsafe { ffi::FPDFLink_GetTextRange(link_page, index, &mut start_char_index, &mut char_count) };
+		if has_range == 0 || char_count <= 0 {
+			continue;
+		}
+		let anchor_text = web_link_anchor_text(text_page, start_char_index, char_count);
+		if anchor_text.trim().is_empty() {
+			continue;
+		}
+		let Some(url) = read_web_link_url(link_page, index) else { continue };
+		let offsets_in_range = (start_char_index..start_char_index + char_count).filter_map(|raw_index| char_offsets.get(&raw_index));
+		let Some(offset) = offsets_in_range.copied().min() else { continue };
+		markers.push(Marker::new(MarkerType::Link, page_start + offset).with_text(anchor_text).with_reference(url));
+	}
+	unsafe {
+		ffi::FPDFLink_CloseWebLinks(link_page);
+	}
+	markers
+}
+
+fn web_link_anchor_text(text_page: &PdfTextPage, start: i32, count: i32) -> String {
+	(start..start + count)
+		.filter_map(|index| char::from_u32(unsafe { ffi::FPDFText_GetUnicode(text_page.handle, index) }))
+		.collect()
+}
+
+fn read_web_link_url(link_page: ffi::FPDF_PAGELINK, index: i32) -> Option<String> {
+	let length = unsafe { ffi::FPDFLink_GetURL(link_page, index, ptr::null_mut(), 0) };
+	if length <= 1 {
+		return None;
+	}
+	let mut buffer = vec![0u16; length as usize];
+	let written = unsafe { ffi::FPDFLink_GetURL(link_page, index, buffer.as_mut_ptr(), length) };
+	if written <= 1 {
+		return None;
+	}
+	sanitize_utf16_buffer(&buffer, (written as u32) * 2)
+}
+
+/// Finds this page's internal GoTo link annotations (`FPDFLink_Enumerate`/`FPDFLink_GetDest`) and
+/// records each one's anchor position/text plus its destination page index; see
+/// `PendingLinkMarker` for why resolving that index into a buffer offset is deferred. The anchor's
+/// position is the lowest reconstructed-text offset among its matched glyphs, looked up by glyph
+/// identity in `char_offsets` rather than by re-finding the anchor text (see `web_link_markers`).
+fn collect_internal_link_markers(
+	document: ffi::FPDF_DOCUMENT,
+	page: &PdfPage,
+	chars: &[CharInfo],
+	char_offsets: &HashMap<i32, usize>,
+	page_start: usize,
+) -> Vec<PendingLinkMarker> {
+	let mut pending = Vec::new();
+	let mut start_pos = 0;
+	loop {
+		let mut link_annot = ptr::null_mut();
+		if unsafe { ffi::FPDFLink_Enumerate(page.handle, &mut start_pos, &mut link_annot) } == 0 {
+			break;
+		}
+		if link_annot.is_null() {
+			continue;
+		}
+		let dest = unsafe { ffi::FPDFLink_GetDest(document, link_annot) };
+		if dest.is_null() {
+			continue;
+		}
+		let dest_page_index = unsafe { ffi::FPDFDest_GetDestPageIndex(document, dest) };
+		if dest_page_index < 0 {
+			continue;
+		}
+		let mut rect = ffi::FS_RECTF { left: 0.0, top: 0.0, right: 0.0, bottom: 0.0 };
+		if unsafe { ffi::FPDFLink_GetAnnotRect(link_annot, &mut rect) } == 0 {
+			continue;
+		}
+		let matched = chars_within_rect(chars, &rect);
+		let anchor_text: String = matched.iter().filter_map(|c| char::from_u32(c.unicode)).collect();
+		if anchor_text.trim().is_empty() {
+			continue;
+		}
+		let Some(offset) = matched.iter().filter_map(|c| char_offsets.get(&c.raw_index)).copied().min() else { continue };
+		pending.push(PendingLinkMarker { position: page_start + offset, anchor_text, dest_page_index });
+	}
+	pending
+}
+
+/// Collects the glyphs whose box center falls inside `rect` (a link annotation's clickable area),
+/// in character-stream order, so callers can recover both the annotation's anchor text and the
+/// `raw_index` of each matched glyph.
+fn chars_within_rect(chars: &[CharInfo], rect: &ffi::FS_RECTF) -> Vec<CharInfo> {
+	let (left, right) = (f64::from(rect.left.min(rect.right)), f64::from(rect.left.max(rect.right)));
+	let (bottom, top) = (f64::from(rect.top.min(rect.bottom)), f64::from(rect.top.max(rect.bottom)));
+	chars
+		.iter()
+		.copied()
+		.filter(|c| {
+			let (cx, cy) = ((c.left + c.right) / 2.0, (c.top + c.bottom) / 2.0);
+			cx >= left && cx <= right && cy >= bottom && cy <= top
+		})
 		.collect()
 }
 
@@ -225,17 +783,8 @@ fn extract_outline_items(
 	while !bookmark.is_null() {
 		let name = read_bookmark_title(bookmark).unwrap_or_default();
 		let offset = unsafe {
-			let dest = ffi::FPDFBookmark_GetDest(document, bookmark);
-			if dest.is_null() {
-				usize::MAX
-			} else {
-				let page_index = ffi::FPDFDest_GetDestPageIndex(document, dest);
-				if page_index < 0 {
-					usize::MAX
-				} else {
-					page_offsets.get(page_index as usize).copied().unwrap_or(usize::MAX)
-				}
-			}
+			let direct = resolve_dest_offset(document, ffi::FPDFBookmark_GetDest(document, bookmark), page_offsets);
+			direct.or_else(|| resolve_action_offset(document, bookmark, page_offsets)).unwrap_or(usize::MAX)
 		};
 		let mut toc_item = TocItem::new(name, String::new(), offset);
 		let child = unsafe { ffi::FPDFBookmark_GetFirstChild(document, bookmark) };
@@ -248,6 +797,33 @@ fn extract_outline_items(
 	items
 }
 
+/// Resolves a bookmark's target page offset via its GoTo *action* rather than a direct
+/// destination - the common case for bookmarks authored by tools that always wrap destinations in
+/// an action, which `FPDFBookmark_GetDest` alone can't see.
+fn resolve_action_offset(
+	document: ffi::FPDF_DOCUMENT,
+	bookmark: ffi::FPDF_BOOKMARK,
+	page_offsets: &[usize],
+) -> Option<usize> {
+	let action = unsafe { ffi::FPDFBookmark_GetAction(bookmark) };
+	if action.is_null() || unsafe { ffi::FPDFAction_GetType(action) } != ffi::PDFACTION_GOTO {
+		return None;
+	}
+	let action_dest = unsafe { ffi::FPDFAction_GetDest(document, action) };
+	resolve_dest_offset(document, action_dest, page_offsets)
+}
+
+fn resolve_dest_offset(document: ffi::FPDF_DOCUMENT, dest: ffi::FPDF_DEST, page_offsets: &[usize]) -> Option<usize> {
+	if dest.is_null() {
+		return None;
+	}
+	let page_index = unsafe { ffi::FPDFDest_GetDestPageIndex(document, dest) };
+	if page_index < 0 {
+		return None;
+	}
+	page_offsets.get(page_index as usize).copied()
+}
+
 fn read_bookmark_title(bookmark: ffi::FPDF_BOOKMARK) -> Option<String> {
 	let length = unsafe { ffi::FPDFBookmark_GetTitle(bookmark, ptr::null_mut(), 0) };
 	if length <= 2 {
@@ -269,6 +845,15 @@ fn sanitize_utf16_buffer(buffer: &[u16], written_bytes: u32) -> Option<String> {
 	buffer.get(..total_units).map(|slice| String::from_utf16_lossy(slice))
 }
 
+fn build_password_cstring(password: Option<&str>) -> Result<Option<CString>> {
+	match password {
+		Some(pwd) if !pwd.is_empty() => {
+			Ok(Some(CString::new(pwd).map_err(|_| anyhow!("PDF password contains embedded NUL bytes"))?))
+		}
+		_ => Ok(None),
+	}
+}
+
 fn map_pdfium_error(default_message: &str) -> anyhow::Error {
 	let last_error = unsafe { ffi::FPDF_GetLastError() };
 	match last_error {
@@ -288,14 +873,29 @@ mod ffi {
 	pub type FPDF_TEXTPAGE = *mut c_void;
 	pub type FPDF_BOOKMARK = *mut c_void;
 	pub type FPDF_DEST = *mut c_void;
+	pub type FPDF_ACTION = *mut c_void;
+	pub type FPDF_BITMAP = *mut c_void;
+	pub type FPDF_PAGELINK = *mut c_void;
+	pub type FPDF_LINK = *mut c_void;
 
 	pub const FPDF_ERR_PASSWORD: u32 = 4;
+	pub const PDFACTION_GOTO: u32 = 1;
+
+	/// Mirrors PDFium's `FS_RECTF`, the page-space rectangle `FPDFLink_GetAnnotRect` fills in.
+	#[repr(C)]
+	pub struct FS_RECTF {
+		pub left: f32,
+		pub top: f32,
+		pub right: f32,
+		pub bottom: f32,
+	}
 
 	#[link(name = "pdfium")]
 	unsafe extern "C" {
 		pub fn FPDF_InitLibrary();
 		pub fn FPDF_DestroyLibrary();
 		pub fn FPDF_LoadDocument(file_path: *const i8, password: *const i8) -> FPDF_DOCUMENT;
+		pub fn FPDF_LoadMemDocument(data_buf: *const c_void, size: i32, password: *const i8) -> FPDF_DOCUMENT;
 		pub fn FPDF_CloseDocument(document: FPDF_DOCUMENT);
 		pub fn FPDF_GetLastError() -> u32;
 		pub fn FPDF_GetPageCount(document: FPDF_DOCUMENT) -> i32;
@@ -304,12 +904,82 @@ mod ffi {
 		pub fn FPDFText_LoadPage(page: FPDF_PAGE) -> FPDF_TEXTPAGE;
 		pub fn FPDFText_ClosePage(text_page: FPDF_TEXTPAGE);
 		pub fn FPDFText_CountChars(text_page: FPDF_TEXTPAGE) -> i32;
-		pub fn FPDFText_GetText(text_page: FPDF_TEXTPAGE, start_index: i32, count: i32, result: *mut u16) -> i32;
+		pub fn FPDFText_GetUnicode(text_page: FPDF_TEXTPAGE, index: i32) -> u32;
+		pub fn FPDFText_GetCharBox(
+			text_page: FPDF_TEXTPAGE,
+			index: i32,
+			left: *mut f64,
+			right: *mut f64,
+			bottom: *mut f64,
+			top: *mut f64,
+		) -> i32;
 		pub fn FPDF_GetMetaText(document: FPDF_DOCUMENT, tag: *const i8, buffer: *mut c_void, buflen: u32) -> u32;
 		pub fn FPDFBookmark_GetFirstChild(document: FPDF_DOCUMENT, bookmark: FPDF_BOOKMARK) -> FPDF_BOOKMARK;
 		pub fn FPDFBookmark_GetNextSibling(document: FPDF_DOCUMENT, bookmark: FPDF_BOOKMARK) -> FPDF_BOOKMARK;
 		pub fn FPDFBookmark_GetTitle(bookmark: FPDF_BOOKMARK, buffer: *mut c_void, buflen: u32) -> u32;
 		pub fn FPDFBookmark_GetDest(document: FPDF_DOCUMENT, bookmark: FPDF_BOOKMARK) -> FPDF_DEST;
+		pub fn FPDFBookmark_GetAction(bookmark: FPDF_BOOKMARK) -> FPDF_ACTION;
+		pub fn FPDFAction_GetType(action: FPDF_ACTION) -> u32;
+		pub fn FPDFAction_GetDest(document: FPDF_DOCUMENT, action: FPDF_ACTION) -> FPDF_DEST;
 		pub fn FPDFDest_GetDestPageIndex(document: FPDF_DOCUMENT, dest: FPDF_DEST) -> i32;
+		pub fn FPDF_GetPageWidthF(page: FPDF_PAGE) -> f32;
+		pub fn FPDF_GetPageHeightF(page: FPDF_PAGE) -> f32;
+		pub fn FPDFBitmap_Create(width: i32, height: i32, alpha: i32) -> FPDF_BITMAP;
+		pub fn FPDFBitmap_GetStride(bitmap: FPDF_BITMAP) -> i32;
+		pub fn FPDFBitmap_GetBuffer(bitmap: FPDF_BITMAP) -> *mut c_void;
+		pub fn FPDFBitmap_Destroy(bitmap: FPDF_BITMAP);
+		#[allow(clippy::too_many_arguments)]
+		pub fn FPDF_RenderPageBitmap(
+			bitmap: FPDF_BITMAP,
+			page: FPDF_PAGE,
+			start_x: i32,
+			start_y: i32,
+			size_x: i32,
+			size_y: i32,
+			rotate: i32,
+			flags: i32,
+		);
+		pub fn FPDFLink_LoadWebLinks(text_page: FPDF_TEXTPAGE) -> FPDF_PAGELINK;
+		pub fn FPDFLink_CloseWebLinks(link_page: FPDF_PAGELINK);
+		pub fn FPDFLink_CountWebLinks(link_page: FPDF_PAGELINK) -> i32;
+		pub fn FPDFLink_GetURL(link_page: FPDF_PAGELINK, link_index: i32, buffer: *mut u16, buflen: i32) -> i32;
+		pub fn FPDFLink_GetTextRange(
+			link_page: FPDF_PAGELINK,
+			link_index: i32,
+			start_char_index: *mut i32,
+			char_count: *mut i32,
+		) -> i32;
+		pub fn FPDFLink_Enumerate(page: FPDF_PAGE, start_pos: *mut i32, link_annot: *mut FPDF_LINK) -> i32;
+		pub fn FPDFLink_GetDest(document: FPDF_DOCUMENT, link: FPDF_LINK) -> FPDF_DEST;
+		pub fn FPDFLink_GetAnnotRect(link_annot: FPDF_LINK, rect: *mut FS_RECTF) -> i32;
+		pub fn FPDF_GetDocPermissions(document: FPDF_DOCUMENT) -> u32;
+		pub fn FPDF_GetSecurityHandlerRevision(document: FPDF_DOCUMENT) -> i32;
+	}
+}
+
+#[cfg(feature = "ocr")]
+mod tesseract_ffi {
+	#![allow(non_camel_case_types)]
+
+	use std::ffi::{c_char, c_int, c_void};
+
+	pub type TessBaseAPI = *mut c_void;
+
+	#[link(name = "tesseract")]
+	unsafe extern "C" {
+		pub fn TessBaseAPICreate() -> TessBaseAPI;
+		pub fn TessBaseAPIDelete(handle: TessBaseAPI);
+		pub fn TessBaseAPIInit3(handle: TessBaseAPI, datapath: *const c_char, language: *const c_char) -> c_int;
+		pub fn TessBaseAPIEnd(handle: TessBaseAPI);
+		pub fn TessBaseAPISetImage(
+			handle: TessBaseAPI,
+			image_data: *const u8,
+			width: i32,
+			height: i32,
+			bytes_per_pixel: i32,
+			bytes_per_line: i32,
+		);
+		pub fn TessBaseAPIGetUTF8Text(handle: TessBaseAPI) -> *mut c_char;
+		pub fn TessDeleteText(text: *mut c_char);
 	}
 }
@@ -1,11 +1,14 @@
-use std::{fs, path::Path};
+use std::{collections::HashMap, fs, path::Path};
 
 use anyhow::{Context, Result};
 
 use crate::{
-	document::{Document, DocumentBuffer, ParserContext, ParserFlags},
+	document::{Document, DocumentBuffer, Marker, MarkerType, ParserContext, ParserFlags},
 	parser::Parser,
-	utils::{encoding::convert_to_utf8, text::remove_soft_hyphens},
+	utils::{
+		encoding::convert_to_utf8,
+		text::{display_len, remove_soft_hyphens},
+	},
 };
 
 pub struct TextParser;
@@ -20,7 +23,7 @@ impl Parser for TextParser {
 	}
 
 	fn supported_flags(&self) -> ParserFlags {
-		ParserFlags::NONE
+		ParserFlags::SUPPORTS_AUTOLINK
 	}
 
 	fn parse(&self, context: &ParserContext) -> Result<Document> {
@@ -31,11 +34,163 @@ impl Parser for TextParser {
 		}
 		let utf8_content = convert_to_utf8(&bytes);
 		let processed = remove_soft_hyphens(&utf8_content);
+		let (link_markers, tags) = extract_autolinks_and_tags(&processed);
 		let title =
 			Path::new(&context.file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled").to_string();
+		let mut buffer = DocumentBuffer::with_content(processed);
+		for marker in link_markers {
+			buffer.add_marker(marker);
+		}
 		let mut doc = Document::new().with_title(title);
-		doc.set_buffer(DocumentBuffer::with_content(processed));
+		doc.set_buffer(buffer);
+		doc.tags = tags;
 		doc.compute_stats();
 		Ok(doc)
 	}
 }
+
+/// A lightweight, `linkify`-style scan over plain prose: recognizes `http(s)://` and `www.` URLs
+/// and bare email addresses as `MarkerType::Link` markers, and indexes `#hashtag` tokens and
+/// `[[wiki link]]` references (as used by gardenserver-style digital gardens) into a tag map keyed
+/// by the bare token text, each mapping to every position it occurs at.
+fn extract_autolinks_and_tags(text: &str) -> (Vec<Marker>, HashMap<String, Vec<usize>>) {
+	let mut markers = Vec::new();
+	let mut tags: HashMap<String, Vec<usize>> = HashMap::new();
+	let chars: Vec<char> = text.chars().collect();
+	let mut pos = 0usize;
+	let mut i = 0usize;
+	while i < chars.len() {
+		if chars[i] == '[' && chars.get(i + 1) == Some(&'[') {
+			if let Some(close) = find_wiki_link_close(&chars, i + 2) {
+				let inner: String = chars[i + 2..close].iter().collect();
+				let whole: String = chars[i..close + 2].iter().collect();
+				let trimmed = inner.trim();
+				if !trimmed.is_empty() {
+					tags.entry(trimmed.to_string()).or_default().push(pos);
+				}
+				pos += display_len(&whole);
+				i = close + 2;
+				continue;
+			}
+		}
+		if chars[i] == '#' && at_word_start(&chars, i) {
+			if let Some(end) = scan_hashtag(&chars, i) {
+				let token: String = chars[i..end].iter().collect();
+				tags.entry(token.clone()).or_default().push(pos);
+				pos += display_len(&token);
+				i = end;
+				continue;
+			}
+		}
+		if at_word_start(&chars, i) {
+			if let Some(end) = scan_url(&chars, i) {
+				let token: String = chars[i..end].iter().collect();
+				let reference = normalize_url(&token);
+				markers.push(Marker::new(MarkerType::Link, pos).with_text(token.clone()).with_reference(reference));
+				pos += display_len(&token);
+				i = end;
+				continue;
+			}
+			if let Some(end) = scan_email(&chars, i) {
+				let token: String = chars[i..end].iter().collect();
+				let reference = format!("mailto:{token}");
+				markers.push(Marker::new(MarkerType::Link, pos).with_text(token.clone()).with_reference(reference));
+				pos += display_len(&token);
+				i = end;
+				continue;
+			}
+		}
+		pos += display_len(&chars[i].to_string());
+		i += 1;
+	}
+	(markers, tags)
+}
+
+/// Finds the index of the closing `]]` for a `[[wiki link]]` opened at `start`, not crossing a
+/// newline (an unterminated `[[` on one line shouldn't swallow the rest of the document).
+fn find_wiki_link_close(chars: &[char], start: usize) -> Option<usize> {
+	let mut j = start;
+	while j + 1 < chars.len() {
+		if chars[j] == ']' && chars[j + 1] == ']' {
+			return Some(j);
+		}
+		if chars[j] == '\n' {
+			return None;
+		}
+		j += 1;
+	}
+	None
+}
+
+fn scan_hashtag(chars: &[char], start: usize) -> Option<usize> {
+	let mut end = start + 1;
+	while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_' || chars[end] == '-') {
+		end += 1;
+	}
+	(end > start + 1).then_some(end)
+}
+
+const URL_PREFIXES: [&str; 3] = ["https://", "http://", "www."];
+
+fn scan_url(chars: &[char], start: usize) -> Option<usize> {
+	let prefix = URL_PREFIXES.iter().find(|prefix| starts_with_at(chars, start, prefix))?;
+	let body_start = start + prefix.chars().count();
+	let mut end = body_start;
+	while end < chars.len() && is_url_char(chars[end]) {
+		end += 1;
+	}
+	while end > body_start && is_trailing_punctuation(chars[end - 1]) {
+		end -= 1;
+	}
+	(end > body_start).then_some(end)
+}
+
+fn scan_email(chars: &[char], start: usize) -> Option<usize> {
+	let mut j = start;
+	while j < chars.len() && is_email_local_char(chars[j]) {
+		j += 1;
+	}
+	if j == start || j >= chars.len() || chars[j] != '@' {
+		return None;
+	}
+	j += 1;
+	let domain_start = j;
+	while j < chars.len() && (chars[j].is_ascii_alphanumeric() || chars[j] == '-' || chars[j] == '.') {
+		j += 1;
+	}
+	while j > domain_start && chars[j - 1] == '.' {
+		j -= 1;
+	}
+	let domain: String = chars[domain_start..j].iter().collect();
+	let last_label = domain.rsplit('.').next().unwrap_or("");
+	let has_valid_tld = domain.contains('.') && last_label.len() >= 2 && last_label.chars().all(char::is_alphabetic);
+	has_valid_tld.then_some(j)
+}
+
+fn normalize_url(token: &str) -> String {
+	if token.starts_with("www.") { format!("https://{token}") } else { token.to_string() }
+}
+
+fn at_word_start(chars: &[char], index: usize) -> bool {
+	index == 0 || !(chars[index - 1].is_alphanumeric() || chars[index - 1] == '_')
+}
+
+fn is_url_char(c: char) -> bool {
+	c.is_alphanumeric() || "-._~:/?#[]@!$&'()*+,;=%".contains(c)
+}
+
+fn is_trailing_punctuation(c: char) -> bool {
+	matches!(c, '.' | ',' | ';' | ':' | '!' | '?' | ')' | ']' | '}' | '\'' | '"')
+}
+
+fn is_email_local_char(c: char) -> bool {
+	c.is_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-')
+}
+
+fn starts_with_at(chars: &[char], index: usize, needle: &str) -> bool {
+	let needle_chars: Vec<char> = needle.chars().collect();
+	if index + needle_chars.len() > chars.len() {
+		return false;
+	}
+	chars[index..index + needle_chars.len()] == needle_chars[..]
+}
@@ -1,8 +1,16 @@
+use std::path::Path;
+
 use crate::{
 	document::{DocumentBuffer, MarkerType, TocItem},
 	html_to_text::HeadingInfo,
 };
 
+/// Derives a document title from a file's name when the format itself carries none, matching the
+/// `file_stem` fallback every parser in this module already falls back to.
+pub fn extract_title_from_path(file_path: &str) -> String {
+	Path::new(file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled").to_string()
+}
+
 pub fn build_toc_from_buffer(buffer: &DocumentBuffer) -> Vec<TocItem> {
 	let headings: Vec<HeadingInfo> = buffer
 		.markers
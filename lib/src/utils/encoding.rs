@@ -0,0 +1,80 @@
+use encoding_rs::{UTF_16BE, UTF_16LE, WINDOWS_1252};
+
+#[must_use]
+pub fn convert_to_utf8(input: &[u8]) -> String {
+	if input.len() >= 4 {
+		if input[0] == 0xFF && input[1] == 0xFE && input[2] == 0x00 && input[3] == 0x00 {
+			return decode_utf32_le(&input[4..]);
+		}
+		if input[0] == 0x00 && input[1] == 0x00 && input[2] == 0xFE && input[3] == 0xFF {
+			return decode_utf32_be(&input[4..]);
+		}
+	}
+	if input.len() >= 3 && input[0] == 0xEF && input[1] == 0xBB && input[2] == 0xBF {
+		return String::from_utf8_lossy(&input[3..]).to_string();
+	}
+	if input.len() >= 2 {
+		if input[0] == 0xFF && input[1] == 0xFE {
+			let (decoded, _, _) = UTF_16LE.decode(&input[2..]);
+			return decoded.to_string();
+		}
+		if input[0] == 0xFE && input[1] == 0xFF {
+			let (decoded, _, _) = UTF_16BE.decode(&input[2..]);
+			return decoded.to_string();
+		}
+	}
+	if let Ok(s) = String::from_utf8(input.to_vec()) {
+		return s;
+	}
+	let (decoded, _, had_errors) = WINDOWS_1252.decode(input);
+	if !had_errors {
+		return decoded.to_string();
+	}
+	String::from_utf8_lossy(input).to_string()
+}
+
+fn decode_utf32_le(input: &[u8]) -> String {
+	let mut result = String::new();
+	let mut i = 0;
+	while i + 3 < input.len() {
+		let code_point = u32::from_le_bytes([input[i], input[i + 1], input[i + 2], input[i + 3]]);
+		if let Some(ch) = char::from_u32(code_point) {
+			result.push(ch);
+		}
+		i += 4;
+	}
+	result
+}
+
+fn decode_utf32_be(input: &[u8]) -> String {
+	let mut result = String::new();
+	let mut i = 0;
+	while i + 3 < input.len() {
+		let code_point = u32::from_be_bytes([input[i], input[i + 1], input[i + 2], input[i + 3]]);
+		if let Some(ch) = char::from_u32(code_point) {
+			result.push(ch);
+		}
+		i += 4;
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_utf8_with_bom() {
+		assert_eq!(convert_to_utf8(b"\xEF\xBB\xBFHello"), "Hello");
+	}
+
+	#[test]
+	fn test_utf16le_with_bom() {
+		assert_eq!(convert_to_utf8(b"\xFF\xFEH\x00e\x00l\x00l\x00o\x00"), "Hello");
+	}
+
+	#[test]
+	fn test_plain_utf8() {
+		assert_eq!(convert_to_utf8(b"Hello World"), "Hello World");
+	}
+}
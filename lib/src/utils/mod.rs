@@ -0,0 +1,3 @@
+pub mod encoding;
+pub mod text;
+pub mod zip;
@@ -0,0 +1,79 @@
+use percent_encoding::percent_decode_str;
+
+#[must_use]
+pub fn remove_soft_hyphens(input: &str) -> String {
+	input.replace('\u{00AD}', "")
+}
+
+#[must_use]
+pub fn url_decode(input: &str) -> String {
+	percent_decode_str(input).decode_utf8_lossy().into_owned()
+}
+
+#[must_use]
+pub fn collapse_whitespace(input: &str) -> String {
+	let mut result = String::with_capacity(input.len());
+	let mut prev_was_space = false;
+	for ch in input.chars() {
+		let is_space = ch.is_whitespace() || ch == '\u{00A0}';
+		if is_space {
+			if !prev_was_space {
+				result.push(' ');
+				prev_was_space = true;
+			}
+		} else {
+			result.push(ch);
+			prev_was_space = false;
+		}
+	}
+	result
+}
+
+/// Trims whitespace and non-breaking spaces from the start and end of a string.
+#[must_use]
+pub fn trim_string(s: &str) -> String {
+	s.trim_matches(|c: char| c.is_whitespace() || c == '\u{00A0}').to_string()
+}
+
+/// Counts the UTF-16 code units a string would occupy, matching the offsets
+/// exposed to the native text controls the FFI layer hands documents to.
+#[must_use]
+pub fn display_len(text: &str) -> usize {
+	text.encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_remove_soft_hyphens() {
+		assert_eq!(remove_soft_hyphens("hel\u{00AD}lo"), "hello");
+		assert_eq!(remove_soft_hyphens("no hyphens"), "no hyphens");
+	}
+
+	#[test]
+	fn test_url_decode() {
+		assert_eq!(url_decode("hello+world"), "hello+world");
+		assert_eq!(url_decode("hello%20world"), "hello world");
+		assert_eq!(url_decode("caf%C3%A9"), "café");
+	}
+
+	#[test]
+	fn test_collapse_whitespace() {
+		assert_eq!(collapse_whitespace("hello   world"), "hello world");
+		assert_eq!(collapse_whitespace("hello\u{00A0}\u{00A0}world"), "hello world");
+	}
+
+	#[test]
+	fn test_trim_string() {
+		assert_eq!(trim_string("  hello  "), "hello");
+		assert_eq!(trim_string("\u{00A0}hello\u{00A0}"), "hello");
+	}
+
+	#[test]
+	fn test_display_len() {
+		assert_eq!(display_len("hello"), 5);
+		assert_eq!(display_len("𝔘"), 2);
+	}
+}
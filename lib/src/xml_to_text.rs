@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+
+use roxmltree::{Document as XmlDocument, Node, NodeType};
+
+pub use crate::html_to_text::{
+	HeadingInfo, LinkInfo, ListInfo, ListItemInfo, NoteInfo, NoteRefInfo, StyleSpanInfo, TableCellInfo, TableInfo,
+	TableRowInfo,
+};
+use crate::document::TextStyle;
+
+pub struct XmlToText {
+	text: String,
+	headings: Vec<HeadingInfo>,
+	links: Vec<LinkInfo>,
+	lists: Vec<ListInfo>,
+	list_items: Vec<ListItemInfo>,
+	style_spans: Vec<StyleSpanInfo>,
+	tables: Vec<TableInfo>,
+	table_rows: Vec<TableRowInfo>,
+	table_cells: Vec<TableCellInfo>,
+	note_refs: Vec<NoteRefInfo>,
+	notes: Vec<NoteInfo>,
+	section_offsets: Vec<usize>,
+	id_positions: HashMap<String, usize>,
+	current_style: TextStyle,
+	section_depth: i32,
+}
+
+impl XmlToText {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			text: String::new(),
+			headings: Vec::new(),
+			links: Vec::new(),
+			lists: Vec::new(),
+			list_items: Vec::new(),
+			style_spans: Vec::new(),
+			tables: Vec::new(),
+			table_rows: Vec::new(),
+			table_cells: Vec::new(),
+			note_refs: Vec::new(),
+			notes: Vec::new(),
+			section_offsets: Vec::new(),
+			id_positions: HashMap::new(),
+			current_style: TextStyle::NONE,
+			section_depth: 0,
+		}
+	}
+
+	pub fn convert(&mut self, content: &str) -> bool {
+		let Ok(doc) = XmlDocument::parse(content) else {
+			return false;
+		};
+		self.walk_children(doc.root());
+		self.finish_section();
+		true
+	}
+
+	#[must_use]
+	pub fn get_text(&self) -> String {
+		self.text.clone()
+	}
+
+	#[must_use]
+	pub fn get_headings(&self) -> &[HeadingInfo] {
+		&self.headings
+	}
+
+	#[must_use]
+	pub fn get_links(&self) -> &[LinkInfo] {
+		&self.links
+	}
+
+	#[must_use]
+	pub fn get_lists(&self) -> &[ListInfo] {
+		&self.lists
+	}
+
+	#[must_use]
+	pub fn get_list_items(&self) -> &[ListItemInfo] {
+		&self.list_items
+	}
+
+	#[must_use]
+	pub fn get_style_spans(&self) -> &[StyleSpanInfo] {
+		&self.style_spans
+	}
+
+	#[must_use]
+	pub fn get_section_offsets(&self) -> &[usize] {
+		&self.section_offsets
+	}
+
+	#[must_use]
+	pub fn get_tables(&self) -> &[TableInfo] {
+		&self.tables
+	}
+
+	#[must_use]
+	pub fn get_table_rows(&self) -> &[TableRowInfo] {
+		&self.table_rows
+	}
+
+	#[must_use]
+	pub fn get_table_cells(&self) -> &[TableCellInfo] {
+		&self.table_cells
+	}
+
+	#[must_use]
+	pub fn get_id_positions(&self) -> &HashMap<String, usize> {
+		&self.id_positions
+	}
+
+	#[must_use]
+	pub fn get_note_refs(&self) -> &[NoteRefInfo] {
+		&self.note_refs
+	}
+
+	#[must_use]
+	pub fn get_notes(&self) -> &[NoteInfo] {
+		&self.notes
+	}
+
+	fn push_style(&mut self, flags: TextStyle) {
+		if flags.is_empty() {
+			return;
+		}
+		let new_style = self.current_style | flags;
+		if new_style != self.current_style {
+			self.current_style = new_style;
+			self.style_spans.push(StyleSpanInfo { offset: self.text.len(), flags: self.current_style });
+		}
+	}
+
+	fn pop_style(&mut self, flags: TextStyle, previous: TextStyle) {
+		if flags.is_empty() {
+			return;
+		}
+		if previous != self.current_style {
+			self.current_style = previous;
+			self.style_spans.push(StyleSpanInfo { offset: self.text.len(), flags: self.current_style });
+		}
+	}
+
+	fn finish_section(&mut self) {
+		if self.current_style != TextStyle::NONE {
+			self.current_style = TextStyle::NONE;
+			self.style_spans.push(StyleSpanInfo { offset: self.text.len(), flags: TextStyle::NONE });
+		}
+	}
+
+	fn ensure_trailing_newline(&mut self) {
+		if !self.text.ends_with('\n') {
+			self.text.push('\n');
+		}
+	}
+
+	fn walk_children(&mut self, node: Node) {
+		for child in node.children() {
+			self.walk_node(child);
+		}
+	}
+
+	fn walk_node(&mut self, node: Node) {
+		match node.node_type() {
+			NodeType::Text => {
+				if let Some(text) = node.text() {
+					self.text.push_str(text);
+				}
+			}
+			NodeType::Element => {
+				let tag_name = node.tag_name().name();
+				let epub_type = node.attribute(("http://www.idpf.org/2007/ops", "type")).or_else(|| node.attribute("epub:type"));
+				if matches!(epub_type, Some("footnote") | Some("endnote") | Some("rearnote")) {
+					if let Some(note_id) = node.attribute("id") {
+						let note_text = extract_plain_text(node);
+						self.notes.push(NoteInfo { id: note_id.to_string(), text: note_text });
+					}
+					return;
+				}
+				if let Some(id) = node.attribute("id") {
+					self.id_positions.insert(id.to_string(), self.text.len());
+				}
+				let style_flags = style_flags_for_tag(tag_name);
+				let previous_style = self.current_style;
+				self.push_style(style_flags);
+				match tag_name {
+					"h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+						let level = tag_name[1..].parse::<i32>().unwrap_or(1);
+						self.emit_heading(node, level);
+					}
+					"title" => {
+						let level = (self.section_depth.max(1)).min(6);
+						self.emit_heading(node, level);
+					}
+					"section" => {
+						self.section_offsets.push(self.text.len());
+						self.section_depth += 1;
+						self.walk_children(node);
+						self.section_depth -= 1;
+						self.ensure_trailing_newline();
+					}
+					"a" => {
+						let reference =
+							node.attribute("href").or_else(|| node.attribute(("http://www.w3.org/1999/xlink", "href")))
+								.unwrap_or("")
+								.to_string();
+						let offset = self.text.len();
+						self.walk_children(node);
+						let link_text = self.text[offset..].to_string();
+						if epub_type == Some("noteref") {
+							if !reference.is_empty() {
+								self.note_refs.push(NoteRefInfo { offset, reference });
+							}
+						} else if !reference.is_empty() && !link_text.is_empty() {
+							self.links.push(LinkInfo { offset, text: link_text, reference });
+						}
+					}
+					"ul" | "ol" => {
+						let list_offset = self.text.len();
+						let item_count = node.children().filter(|c| c.tag_name().name() == "li").count();
+						self.lists.push(ListInfo { offset: list_offset, item_count: item_count as i32 });
+						self.walk_children(node);
+						self.ensure_trailing_newline();
+					}
+					"li" => {
+						let offset = self.text.len();
+						self.walk_children(node);
+						let item_text = self.text[offset..].trim().to_string();
+						self.list_items.push(ListItemInfo { offset, text: item_text, level: 1 });
+						self.ensure_trailing_newline();
+					}
+					"table" => self.process_table(node),
+					"br" => self.text.push('\n'),
+					"p" | "div" | "empty-line" => {
+						self.walk_children(node);
+						self.ensure_trailing_newline();
+					}
+					_ => self.walk_children(node),
+				}
+				self.pop_style(style_flags, previous_style);
+			}
+			_ => {}
+		}
+	}
+
+	fn process_table(&mut self, table: Node) {
+		let rows = collect_table_rows(table);
+		let row_count = rows.len();
+		let col_count = rows.iter().map(|row| collect_table_cells(*row).len()).max().unwrap_or(0);
+		let table_offset = self.text.len();
+		self.tables.push(TableInfo { offset: table_offset, row_count: row_count as i32, col_count: col_count as i32 });
+		for (row_index, row) in rows.iter().enumerate() {
+			let row_offset = self.text.len();
+			self.table_rows.push(TableRowInfo { offset: row_offset, row_index: row_index as i32 });
+			let cells = collect_table_cells(*row);
+			let cell_count = cells.len();
+			for (col_index, cell) in cells.iter().enumerate() {
+				let cell_offset = self.text.len();
+				self.walk_children(*cell);
+				let cell_text = self.text[cell_offset..].trim().to_string();
+				self.table_cells.push(TableCellInfo {
+					offset: cell_offset,
+					row_index: row_index as i32,
+					col_index: col_index as i32,
+					text: cell_text,
+				});
+				if col_index + 1 < cell_count {
+					self.text.push('\t');
+				}
+			}
+			self.ensure_trailing_newline();
+		}
+		self.ensure_trailing_newline();
+	}
+
+	fn emit_heading(&mut self, node: Node, level: i32) {
+		let offset = self.text.len();
+		self.walk_children(node);
+		let heading_text = self.text[offset..].trim().to_string();
+		if !heading_text.is_empty() {
+			self.headings.push(HeadingInfo { offset, level, text: heading_text });
+		}
+		self.ensure_trailing_newline();
+	}
+}
+
+impl Default for XmlToText {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+fn extract_plain_text(node: Node) -> String {
+	let mut text = String::new();
+	for descendant in node.descendants() {
+		if descendant.node_type() == NodeType::Text {
+			if let Some(t) = descendant.text() {
+				text.push_str(t);
+			}
+		}
+	}
+	text.trim().to_string()
+}
+
+fn collect_table_rows(node: Node) -> Vec<Node> {
+	let mut rows = Vec::new();
+	for child in node.children() {
+		if child.node_type() != NodeType::Element {
+			continue;
+		}
+		match child.tag_name().name() {
+			"tr" => rows.push(child),
+			"thead" | "tbody" | "tfoot" => rows.extend(collect_table_rows(child)),
+			_ => {}
+		}
+	}
+	rows
+}
+
+fn collect_table_cells(row: Node) -> Vec<Node> {
+	row.children()
+		.filter(|child| child.node_type() == NodeType::Element && matches!(child.tag_name().name(), "td" | "th"))
+		.collect()
+}
+
+fn style_flags_for_tag(tag_name: &str) -> TextStyle {
+	match tag_name {
+		"b" | "strong" => TextStyle::BOLD,
+		"i" | "em" | "emphasis" => TextStyle::ITALIC,
+		"u" | "underline" => TextStyle::UNDERLINE,
+		"s" | "strike" | "strikethrough" => TextStyle::STRIKETHROUGH,
+		_ => TextStyle::NONE,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_basic_conversion() {
+		let mut converter = XmlToText::new();
+		assert!(converter.convert("<html><body><h1>Title</h1><p>Hello <b>world</b></p></body></html>"));
+		assert!(converter.get_text().contains("Hello"));
+		assert_eq!(converter.get_headings().len(), 1);
+	}
+
+	#[test]
+	fn test_invalid_xml_fails() {
+		let mut converter = XmlToText::new();
+		assert!(!converter.convert("<html><body>unclosed"));
+	}
+
+	#[test]
+	fn test_style_spans() {
+		let mut converter = XmlToText::new();
+		converter.convert("<p>plain <b>bold</b> plain</p>");
+		let spans = converter.get_style_spans();
+		assert_eq!(spans.len(), 2);
+		assert_eq!(spans[0].flags, TextStyle::BOLD);
+		assert_eq!(spans[1].flags, TextStyle::NONE);
+	}
+
+	#[test]
+	fn test_fb2_sections() {
+		let mut converter = XmlToText::new();
+		converter.convert("<FictionBook><body><section><title>Ch 1</title><p>Text</p></section></body></FictionBook>");
+		assert_eq!(converter.get_section_offsets().len(), 1);
+		assert_eq!(converter.get_headings().len(), 1);
+	}
+
+	#[test]
+	fn test_table_extraction() {
+		let mut converter = XmlToText::new();
+		converter.convert("<table><tr><td>A1</td><td>B1</td></tr><tr><td>A2</td><td>B2</td></tr></table>");
+		assert_eq!(converter.get_tables().len(), 1);
+		assert_eq!(converter.get_tables()[0].row_count, 2);
+		assert_eq!(converter.get_tables()[0].col_count, 2);
+		assert_eq!(converter.get_table_cells().len(), 4);
+		assert_eq!(converter.get_table_cells()[3].row_index, 1);
+		assert_eq!(converter.get_table_cells()[3].col_index, 1);
+		assert_eq!(converter.get_table_cells()[3].text, "B2");
+	}
+
+	#[test]
+	fn test_noteref_and_note_extraction() {
+		let mut converter = XmlToText::new();
+		converter.convert(
+			"<html xmlns:epub=\"http://www.idpf.org/2007/ops\"><body>\
+			<p>See<a href=\"#fn1\" epub:type=\"noteref\">1</a></p>\
+			<aside id=\"fn1\" epub:type=\"footnote\"><p>Note body.</p></aside>\
+			</body></html>",
+		);
+		assert_eq!(converter.get_note_refs().len(), 1);
+		assert_eq!(converter.get_note_refs()[0].reference, "#fn1");
+		assert_eq!(converter.get_links().len(), 0);
+		assert_eq!(converter.get_notes().len(), 1);
+		assert_eq!(converter.get_notes()[0].id, "fn1");
+		assert_eq!(converter.get_notes()[0].text, "Note body.");
+		assert!(!converter.get_text().contains("Note body."));
+	}
+}
@@ -2,16 +2,27 @@
 
 use std::{
 	ffi::{CStr, CString},
-	os::raw::c_char,
-	ptr,
+	fs::{self, File, OpenOptions},
+	io::{Read, Write},
+	os::raw::{c_char, c_void},
+	path::{Path, PathBuf},
+	ptr, thread,
 	time::Duration,
 };
 
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, Response};
 use semver::Version;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 
 const RELEASE_URL: &str = "https://api.github.com/repos/trypsynth/paperback/releases/latest";
+const RELEASES_LIST_URL: &str = "https://api.github.com/repos/trypsynth/paperback/releases";
+const DOWNLOAD_TIMEOUT_SECS: u64 = 300;
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+const DEFAULT_UPDATE_TIMEOUT_SECS: u64 = 15;
+const DEFAULT_UPDATE_MAX_ATTEMPTS: u32 = 3;
+const RETRY_BASE_DELAY_MS: u64 = 250;
+const RETRY_MAX_DELAY_MS: u64 = 1000;
 
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -26,6 +37,13 @@ pub enum paperback_update_status {
 	PAPERBACK_UPDATE_STATUS_INTERNAL_ERROR = 7,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum paperback_update_channel {
+	PAPERBACK_UPDATE_CHANNEL_STABLE = 0,
+	PAPERBACK_UPDATE_CHANNEL_BETA = 1,
+}
+
 #[repr(C)]
 pub struct paperback_update_result {
 	pub status: paperback_update_status,
@@ -33,9 +51,34 @@ pub struct paperback_update_result {
 	pub latest_version: *mut c_char,
 	pub download_url: *mut c_char,
 	pub release_notes: *mut c_char,
+	pub expected_sha256: *mut c_char,
 	pub error_message: *mut c_char,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum paperback_download_status {
+	PAPERBACK_DOWNLOAD_STATUS_COMPLETED = 0,
+	PAPERBACK_DOWNLOAD_STATUS_RESUMED = 1,
+	PAPERBACK_DOWNLOAD_STATUS_RESTARTED = 2,
+	PAPERBACK_DOWNLOAD_STATUS_HTTP_ERROR = 3,
+	PAPERBACK_DOWNLOAD_STATUS_NETWORK_ERROR = 4,
+	PAPERBACK_DOWNLOAD_STATUS_IO_ERROR = 5,
+	PAPERBACK_DOWNLOAD_STATUS_INVALID_INPUT = 6,
+	PAPERBACK_DOWNLOAD_STATUS_CHECKSUM_MISMATCH = 7,
+}
+
+#[repr(C)]
+pub struct paperback_download_result {
+	pub status: paperback_download_status,
+	pub http_status: i32,
+	pub bytes_downloaded: u64,
+	pub sha256: *mut c_char,
+	pub error_message: *mut c_char,
+}
+
+pub type paperback_download_progress_callback = extern fn(bytes_downloaded: u64, total_bytes: u64, user_data: *mut c_void);
+
 #[derive(Debug, Deserialize)]
 struct ReleaseAsset {
 	name: String,
@@ -50,7 +93,7 @@ struct GithubRelease {
 }
 
 enum UpdateOutcome {
-	Available { latest_version: String, release_notes: String, download_url: String },
+	Available { latest_version: String, release_notes: String, download_url: String, expected_sha256: Option<String> },
 	UpToDate { latest_version: String },
 	HttpError { status: i32 },
 	NetworkError { message: String },
@@ -59,6 +102,16 @@ enum UpdateOutcome {
 	InvalidInput { message: String },
 }
 
+enum DownloadOutcome {
+	Completed { bytes_downloaded: u64, sha256: String },
+	Resumed { bytes_downloaded: u64, sha256: String },
+	Restarted { bytes_downloaded: u64, sha256: String },
+	HttpError { status: i32 },
+	NetworkError { message: String },
+	IoError { message: String },
+	ChecksumMismatch { expected: String, actual: String },
+}
+
 fn sanitize_for_c(text: &str) -> String {
 	text.replace('\0', " ")
 }
@@ -91,36 +144,143 @@ fn parse_semver_value(value: &str) -> Option<Version> {
 	Version::parse(normalized).ok()
 }
 
-fn pick_download_url(is_installer: bool, assets: &[ReleaseAsset]) -> Option<String> {
+fn pick_download_url(is_installer: bool, assets: &[ReleaseAsset]) -> Option<(String, String)> {
 	let preferred_name = if is_installer { "paperback_setup.exe" } else { "paperback.zip" };
 	for asset in assets {
 		if asset.name.eq_ignore_ascii_case(preferred_name) {
-			return Some(asset.browser_download_url.clone());
+			return Some((asset.browser_download_url.clone(), asset.name.clone()));
+		}
+	}
+	None
+}
+
+fn find_checksum_asset(assets: &[ReleaseAsset]) -> Option<&ReleaseAsset> {
+	assets.iter().find(|asset| {
+		let lower = asset.name.to_ascii_lowercase();
+		lower == "sha256sums" || lower == "sha256sums.txt" || lower == "checksums.txt" || lower.ends_with(".sha256")
+	})
+}
+
+fn parse_checksum_digest(contents: &str, filename: &str) -> Option<String> {
+	for line in contents.lines() {
+		let mut parts = line.split_whitespace();
+		let (Some(digest), Some(name)) = (parts.next(), parts.next()) else {
+			continue;
+		};
+		if name.trim_start_matches('*').eq_ignore_ascii_case(filename) {
+			return Some(digest.to_ascii_lowercase());
 		}
 	}
 	None
 }
 
-fn fetch_latest_release(user_agent: &str) -> Result<GithubRelease, UpdateOutcome> {
-	let client = Client::builder()
-		.user_agent(user_agent)
-		.timeout(Duration::from_secs(15))
-		.build()
-		.map_err(|err| UpdateOutcome::NetworkError { message: format!("Failed to create HTTP client: {err}") })?;
-	match client.get(RELEASE_URL).header("Accept", "application/vnd.github+json").send() {
-		Ok(resp) => {
-			if !resp.status().is_success() {
-				return Err(UpdateOutcome::HttpError { status: resp.status().as_u16() as i32 });
+fn fetch_checksum_digest(client: &Client, assets: &[ReleaseAsset], filename: &str, max_attempts: u32) -> Option<String> {
+	let asset = find_checksum_asset(assets)?;
+	let response = send_with_retry(client.get(&asset.browser_download_url), max_attempts).ok()?;
+	let contents = response.text().ok()?;
+	parse_checksum_digest(&contents, filename)
+}
+
+/// Retries an idempotent GET request up to `max_attempts` times with exponential backoff,
+/// retrying only on network errors and 500/502/503/504 responses; 4xx responses and a
+/// successful response both return immediately.
+fn send_with_retry(request: reqwest::blocking::RequestBuilder, max_attempts: u32) -> Result<Response, UpdateOutcome> {
+	let max_attempts = max_attempts.max(1);
+	let mut last_error = None;
+	for attempt in 0..max_attempts {
+		let Some(attempt_request) = request.try_clone() else {
+			return request
+				.send()
+				.map_err(|err| UpdateOutcome::NetworkError { message: format!("Network error: {err}") });
+		};
+		match attempt_request.send() {
+			Ok(resp) => {
+				let status = resp.status();
+				if status.is_success() {
+					return Ok(resp);
+				}
+				if !is_retryable_status(status.as_u16()) {
+					return Err(UpdateOutcome::HttpError { status: status.as_u16() as i32 });
+				}
+				last_error = Some(UpdateOutcome::HttpError { status: status.as_u16() as i32 });
 			}
-			resp.json::<GithubRelease>().map_err(|err| UpdateOutcome::InvalidResponse {
-				message: format!("Failed to parse release JSON: {err}"),
-			})
+			Err(err) => last_error = Some(UpdateOutcome::NetworkError { message: format!("Network error: {err}") }),
+		}
+		if attempt + 1 < max_attempts {
+			thread::sleep(retry_backoff_delay(attempt));
+		}
+	}
+	Err(last_error.unwrap_or(UpdateOutcome::NetworkError { message: "Request failed after retries.".to_string() }))
+}
+
+const fn is_retryable_status(status: u16) -> bool {
+	matches!(status, 500 | 502 | 503 | 504)
+}
+
+fn retry_backoff_delay(attempt: u32) -> Duration {
+	let millis = RETRY_BASE_DELAY_MS.saturating_mul(1u64 << attempt.min(2));
+	Duration::from_millis(millis.min(RETRY_MAX_DELAY_MS))
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+	if a.len() != b.len() {
+		return false;
+	}
+	let mut diff = 0u8;
+	for (x, y) in a.bytes().zip(b.bytes()) {
+		diff |= x ^ y;
+	}
+	diff == 0
+}
+
+fn fetch_single_release(client: &Client, url: &str, max_attempts: u32) -> Result<GithubRelease, UpdateOutcome> {
+	let request = client.get(url).header("Accept", "application/vnd.github+json");
+	let resp = send_with_retry(request, max_attempts)?;
+	resp.json::<GithubRelease>()
+		.map_err(|err| UpdateOutcome::InvalidResponse { message: format!("Failed to parse release JSON: {err}") })
+}
+
+fn fetch_release_list(client: &Client, url: &str, max_attempts: u32) -> Result<Vec<GithubRelease>, UpdateOutcome> {
+	let request = client.get(url).header("Accept", "application/vnd.github+json");
+	let resp = send_with_retry(request, max_attempts)?;
+	resp.json::<Vec<GithubRelease>>().map_err(|err| UpdateOutcome::InvalidResponse {
+		message: format!("Failed to parse release list JSON: {err}"),
+	})
+}
+
+fn fetch_latest_release(
+	client: &Client,
+	channel: paperback_update_channel,
+	max_attempts: u32,
+) -> Result<GithubRelease, UpdateOutcome> {
+	match channel {
+		paperback_update_channel::PAPERBACK_UPDATE_CHANNEL_STABLE => {
+			fetch_single_release(client, RELEASE_URL, max_attempts)
+		}
+		paperback_update_channel::PAPERBACK_UPDATE_CHANNEL_BETA => {
+			// GitHub's `/releases` endpoint is sorted by creation date, not version, and includes
+			// prereleases, so pick the highest semver tag (prerelease ordering included) ourselves.
+			let releases = fetch_release_list(client, RELEASES_LIST_URL, max_attempts)?;
+			releases
+				.into_iter()
+				.filter_map(|release| parse_semver_value(&release.tag_name).map(|version| (version, release)))
+				.max_by(|(a, _), (b, _)| a.cmp(b))
+				.map(|(_, release)| release)
+				.ok_or_else(|| UpdateOutcome::InvalidResponse {
+					message: "No release with a valid semantic version tag was found.".to_string(),
+				})
 		}
-		Err(err) => Err(UpdateOutcome::NetworkError { message: format!("Network error: {err}") }),
 	}
 }
 
-fn run_update_check(current_version: &str, is_installer: bool, user_agent: &str) -> UpdateOutcome {
+fn run_update_check(
+	current_version: &str,
+	is_installer: bool,
+	channel: paperback_update_channel,
+	timeout_secs: u32,
+	max_attempts: u32,
+	user_agent: &str,
+) -> UpdateOutcome {
 	let current = match parse_semver_value(current_version) {
 		Some(v) => v,
 		None => {
@@ -129,7 +289,13 @@ fn run_update_check(current_version: &str, is_installer: bool, user_agent: &str)
 			}
 		}
 	};
-	let release = match fetch_latest_release(user_agent) {
+	let timeout_secs = if timeout_secs == 0 { DEFAULT_UPDATE_TIMEOUT_SECS } else { u64::from(timeout_secs) };
+	let max_attempts = if max_attempts == 0 { DEFAULT_UPDATE_MAX_ATTEMPTS } else { max_attempts };
+	let client = match Client::builder().user_agent(user_agent).timeout(Duration::from_secs(timeout_secs)).build() {
+		Ok(client) => client,
+		Err(err) => return UpdateOutcome::NetworkError { message: format!("Failed to create HTTP client: {err}") },
+	};
+	let release = match fetch_latest_release(&client, channel, max_attempts) {
 		Ok(rel) => rel,
 		Err(err) => return err,
 	};
@@ -145,34 +311,154 @@ fn run_update_check(current_version: &str, is_installer: bool, user_agent: &str)
 	if current >= latest_semver {
 		return UpdateOutcome::UpToDate { latest_version: release.tag_name };
 	}
-	let download_url = match release.assets.as_ref() {
-		Some(list) if !list.is_empty() => match pick_download_url(is_installer, list) {
-			Some(url) => url,
-			None => {
-				return UpdateOutcome::NoDownload {
-					message: "Update is available but no matching download asset was found.".to_string(),
-				}
-			}
-		},
+	let assets = match release.assets.as_ref() {
+		Some(list) if !list.is_empty() => list,
 		_ => {
 			return UpdateOutcome::NoDownload {
 				message: "Latest release does not include downloadable assets.".to_string(),
 			}
 		}
 	};
+	let (download_url, asset_name) = match pick_download_url(is_installer, assets) {
+		Some(pair) => pair,
+		None => {
+			return UpdateOutcome::NoDownload {
+				message: "Update is available but no matching download asset was found.".to_string(),
+			}
+		}
+	};
+	let expected_sha256 = fetch_checksum_digest(&client, assets, &asset_name, max_attempts);
 	UpdateOutcome::Available {
 		latest_version: release.tag_name,
 		release_notes: release.body.unwrap_or_default(),
 		download_url,
+		expected_sha256,
+	}
+}
+
+fn partial_path(destination: &Path) -> PathBuf {
+	let mut partial = destination.as_os_str().to_os_string();
+	partial.push(".partial");
+	PathBuf::from(partial)
+}
+
+fn run_download(
+	url: &str,
+	destination: &Path,
+	user_agent: &str,
+	expected_sha256: Option<&str>,
+	progress_callback: Option<paperback_download_progress_callback>,
+	user_data: *mut c_void,
+) -> DownloadOutcome {
+	let client = match Client::builder().user_agent(user_agent).timeout(Duration::from_secs(DOWNLOAD_TIMEOUT_SECS)).build() {
+		Ok(client) => client,
+		Err(err) => return DownloadOutcome::NetworkError { message: format!("Failed to create HTTP client: {err}") },
+	};
+	let partial = partial_path(destination);
+	let existing_len = fs::metadata(&partial).map(|meta| meta.len()).unwrap_or(0);
+	let mut request = client.get(url);
+	if existing_len > 0 {
+		request = request.header("Range", format!("bytes={existing_len}-"));
+	}
+	let mut response = match request.send() {
+		Ok(resp) => resp,
+		Err(err) => return DownloadOutcome::NetworkError { message: format!("Network error: {err}") },
+	};
+	let status = response.status();
+	let (mut file, mut downloaded, resumed) = if existing_len > 0 && status.as_u16() == 206 {
+		match OpenOptions::new().append(true).open(&partial) {
+			Ok(file) => (file, existing_len, true),
+			Err(err) => return DownloadOutcome::IoError { message: format!("Failed to open partial file: {err}") },
+		}
+	} else if status.is_success() {
+		match File::create(&partial) {
+			Ok(file) => (file, 0, false),
+			Err(err) => return DownloadOutcome::IoError { message: format!("Failed to create partial file: {err}") },
+		}
+	} else {
+		return DownloadOutcome::HttpError { status: status.as_u16() as i32 };
+	};
+	let restarted = existing_len > 0 && !resumed;
+	let total_bytes = downloaded + response.content_length().unwrap_or(0);
+	let mut buffer = [0u8; DOWNLOAD_CHUNK_SIZE];
+	loop {
+		let read = match response.read(&mut buffer) {
+			Ok(0) => break,
+			Ok(n) => n,
+			Err(err) => return DownloadOutcome::IoError { message: format!("Failed to read response body: {err}") },
+		};
+		if let Err(err) = file.write_all(&buffer[..read]) {
+			return DownloadOutcome::IoError { message: format!("Failed to write to partial file: {err}") };
+		}
+		downloaded += read as u64;
+		if let Some(callback) = progress_callback {
+			callback(downloaded, total_bytes, user_data);
+		}
+	}
+	if let Err(err) = file.sync_all() {
+		return DownloadOutcome::IoError { message: format!("Failed to flush partial file: {err}") };
+	}
+	drop(file);
+	let sha256 = match hash_file(&partial) {
+		Ok(digest) => digest,
+		Err(message) => return DownloadOutcome::IoError { message },
+	};
+	if let Some(expected) = expected_sha256 {
+		if !constant_time_eq(&sha256, &expected.to_ascii_lowercase()) {
+			let _ = fs::remove_file(&partial);
+			return DownloadOutcome::ChecksumMismatch { expected: expected.to_ascii_lowercase(), actual: sha256 };
+		}
+	}
+	if let Err(err) = fs::rename(&partial, destination) {
+		return DownloadOutcome::IoError { message: format!("Failed to finalize download: {err}") };
+	}
+	if restarted {
+		DownloadOutcome::Restarted { bytes_downloaded: downloaded, sha256 }
+	} else if resumed {
+		DownloadOutcome::Resumed { bytes_downloaded: downloaded, sha256 }
+	} else {
+		DownloadOutcome::Completed { bytes_downloaded: downloaded, sha256 }
 	}
 }
 
+fn hash_file(path: &Path) -> Result<String, String> {
+	let mut file = File::open(path).map_err(|err| format!("Failed to reopen downloaded file for hashing: {err}"))?;
+	let mut hasher = Sha256::new();
+	let mut buffer = [0u8; DOWNLOAD_CHUNK_SIZE];
+	loop {
+		let read = file.read(&mut buffer).map_err(|err| format!("Failed to read downloaded file for hashing: {err}"))?;
+		if read == 0 {
+			break;
+		}
+		hasher.update(&buffer[..read]);
+	}
+	Ok(hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+fn make_download_result(
+	status: paperback_download_status,
+	http_status: i32,
+	bytes_downloaded: u64,
+	sha256: Option<String>,
+	error_message: Option<String>,
+) -> *mut paperback_download_result {
+	let result = paperback_download_result {
+		status,
+		http_status,
+		bytes_downloaded,
+		sha256: opt_string_to_c(sha256),
+		error_message: opt_string_to_c(error_message),
+	};
+	Box::into_raw(Box::new(result))
+}
+
 fn make_result(
 	status: paperback_update_status,
 	http_status: i32,
 	latest_version: Option<String>,
 	download_url: Option<String>,
 	release_notes: Option<String>,
+	expected_sha256: Option<String>,
 	error_message: Option<String>,
 ) -> *mut paperback_update_result {
 	let result = paperback_update_result {
@@ -181,6 +467,7 @@ fn make_result(
 		latest_version: opt_string_to_c(latest_version),
 		download_url: opt_string_to_c(download_url),
 		release_notes: opt_string_to_c(release_notes),
+		expected_sha256: opt_string_to_c(expected_sha256),
 		error_message: opt_string_to_c(error_message),
 	};
 	Box::into_raw(Box::new(result))
@@ -202,6 +489,9 @@ fn ptr_to_string(ptr: *const c_char) -> Result<String, String> {
 pub extern fn paperback_check_for_updates(
 	current_version: *const c_char,
 	is_installer_flag: u8,
+	channel_flag: u8,
+	timeout_secs: u32,
+	max_attempts: u32,
 ) -> *mut paperback_update_result {
 	let current_version_value = match ptr_to_string(current_version) {
 		Ok(value) => value,
@@ -212,20 +502,26 @@ pub extern fn paperback_check_for_updates(
 				None,
 				None,
 				None,
+				None,
 				Some(message),
 			)
 		}
 	};
 	let user_agent = format!("paperback/{}", env!("CARGO_PKG_VERSION"));
 	let is_installer = is_installer_flag != 0;
-	let outcome = run_update_check(&current_version_value, is_installer, &user_agent);
+	let channel = match channel_flag {
+		0 => paperback_update_channel::PAPERBACK_UPDATE_CHANNEL_STABLE,
+		_ => paperback_update_channel::PAPERBACK_UPDATE_CHANNEL_BETA,
+	};
+	let outcome = run_update_check(&current_version_value, is_installer, channel, timeout_secs, max_attempts, &user_agent);
 	match outcome {
-		UpdateOutcome::Available { latest_version, release_notes, download_url } => make_result(
+		UpdateOutcome::Available { latest_version, release_notes, download_url, expected_sha256 } => make_result(
 			paperback_update_status::PAPERBACK_UPDATE_STATUS_AVAILABLE,
 			0,
 			Some(latest_version),
 			Some(download_url),
 			Some(release_notes),
+			expected_sha256,
 			None,
 		),
 		UpdateOutcome::UpToDate { latest_version } => make_result(
@@ -235,6 +531,7 @@ pub extern fn paperback_check_for_updates(
 			None,
 			None,
 			None,
+			None,
 		),
 		UpdateOutcome::HttpError { status } => make_result(
 			paperback_update_status::PAPERBACK_UPDATE_STATUS_HTTP_ERROR,
@@ -242,6 +539,7 @@ pub extern fn paperback_check_for_updates(
 			None,
 			None,
 			None,
+			None,
 			Some(format!("GitHub returned HTTP status {status}.")),
 		),
 		UpdateOutcome::NetworkError { message } => make_result(
@@ -250,6 +548,7 @@ pub extern fn paperback_check_for_updates(
 			None,
 			None,
 			None,
+			None,
 			Some(message),
 		),
 		UpdateOutcome::InvalidResponse { message } => make_result(
@@ -258,6 +557,7 @@ pub extern fn paperback_check_for_updates(
 			None,
 			None,
 			None,
+			None,
 			Some(message),
 		),
 		UpdateOutcome::NoDownload { message } => make_result(
@@ -266,6 +566,7 @@ pub extern fn paperback_check_for_updates(
 			None,
 			None,
 			None,
+			None,
 			Some(message),
 		),
 		UpdateOutcome::InvalidInput { message } => make_result(
@@ -274,6 +575,7 @@ pub extern fn paperback_check_for_updates(
 			None,
 			None,
 			None,
+			None,
 			Some(message),
 		),
 	}
@@ -288,6 +590,110 @@ pub extern fn paperback_free_update_result(result: *mut paperback_update_result)
 		drop_c_string((*result).latest_version);
 		drop_c_string((*result).download_url);
 		drop_c_string((*result).release_notes);
+		drop_c_string((*result).expected_sha256);
+		drop_c_string((*result).error_message);
+		drop(Box::from_raw(result));
+	}
+}
+
+#[no_mangle]
+pub extern fn paperback_download_update(
+	url: *const c_char,
+	destination: *const c_char,
+	expected_sha256: *const c_char,
+	progress_callback: Option<paperback_download_progress_callback>,
+	user_data: *mut c_void,
+) -> *mut paperback_download_result {
+	let url_value = match ptr_to_string(url) {
+		Ok(value) => value,
+		Err(message) => {
+			return make_download_result(
+				paperback_download_status::PAPERBACK_DOWNLOAD_STATUS_INVALID_INPUT,
+				0,
+				0,
+				None,
+				Some(message),
+			)
+		}
+	};
+	let destination_value = match ptr_to_string(destination) {
+		Ok(value) => value,
+		Err(message) => {
+			return make_download_result(
+				paperback_download_status::PAPERBACK_DOWNLOAD_STATUS_INVALID_INPUT,
+				0,
+				0,
+				None,
+				Some(message),
+			)
+		}
+	};
+	let expected_sha256_value = if expected_sha256.is_null() { None } else { ptr_to_string(expected_sha256).ok() };
+	let user_agent = format!("paperback/{}", env!("CARGO_PKG_VERSION"));
+	let outcome = run_download(
+		&url_value,
+		Path::new(&destination_value),
+		&user_agent,
+		expected_sha256_value.as_deref(),
+		progress_callback,
+		user_data,
+	);
+	match outcome {
+		DownloadOutcome::Completed { bytes_downloaded, sha256 } => make_download_result(
+			paperback_download_status::PAPERBACK_DOWNLOAD_STATUS_COMPLETED,
+			0,
+			bytes_downloaded,
+			Some(sha256),
+			None,
+		),
+		DownloadOutcome::Resumed { bytes_downloaded, sha256 } => make_download_result(
+			paperback_download_status::PAPERBACK_DOWNLOAD_STATUS_RESUMED,
+			0,
+			bytes_downloaded,
+			Some(sha256),
+			None,
+		),
+		DownloadOutcome::Restarted { bytes_downloaded, sha256 } => make_download_result(
+			paperback_download_status::PAPERBACK_DOWNLOAD_STATUS_RESTARTED,
+			0,
+			bytes_downloaded,
+			Some(sha256),
+			None,
+		),
+		DownloadOutcome::HttpError { status } => make_download_result(
+			paperback_download_status::PAPERBACK_DOWNLOAD_STATUS_HTTP_ERROR,
+			status,
+			0,
+			None,
+			Some(format!("Server returned HTTP status {status}.")),
+		),
+		DownloadOutcome::NetworkError { message } => make_download_result(
+			paperback_download_status::PAPERBACK_DOWNLOAD_STATUS_NETWORK_ERROR,
+			0,
+			0,
+			None,
+			Some(message),
+		),
+		DownloadOutcome::IoError { message } => {
+			make_download_result(paperback_download_status::PAPERBACK_DOWNLOAD_STATUS_IO_ERROR, 0, 0, None, Some(message))
+		}
+		DownloadOutcome::ChecksumMismatch { expected, actual } => make_download_result(
+			paperback_download_status::PAPERBACK_DOWNLOAD_STATUS_CHECKSUM_MISMATCH,
+			0,
+			0,
+			Some(actual.clone()),
+			Some(format!("Downloaded file hash {actual} did not match expected hash {expected}.")),
+		),
+	}
+}
+
+#[no_mangle]
+pub extern fn paperback_free_download_result(result: *mut paperback_download_result) {
+	if result.is_null() {
+		return;
+	}
+	unsafe {
+		drop_c_string((*result).sha256);
 		drop_c_string((*result).error_message);
 		drop(Box::from_raw(result));
 	}